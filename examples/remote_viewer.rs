@@ -0,0 +1,45 @@
+//! Shows the screen mirrored by a `RemoteMirror`, read from the standard input.
+//!
+//! On Linux, with the device sending on a USB serial adapter:
+//!
+//! ```sh
+//! stty -F /dev/ttyUSB0 115200 raw
+//! cargo run --example remote_viewer --features embedded-io < /dev/ttyUSB0
+//! ```
+
+use std::io::{self, Read, Write};
+
+use i2c_lcd_screen::RemoteScreen;
+
+fn main() -> io::Result<()> {
+    let mut screen = RemoteScreen::new();
+    let mut stdout = io::stdout();
+
+    for byte in io::stdin().lock().bytes() {
+        if !screen.feed(byte?) {
+            continue;
+        }
+
+        // Clear the terminal, then draw the screen in a frame
+        let border = "-".repeat(screen.cols() as usize);
+        write!(stdout, "\x1b[2J\x1b[H+{border}+\r\n")?;
+        for row in 0..screen.rows() {
+            let line: String = screen
+                .row(row)
+                .unwrap_or_default()
+                .iter()
+                .map(|code| match code {
+                    // Custom characters
+                    0..=7 => char::from(b'0' + code),
+                    0x20..=0x7e => char::from(*code),
+                    _ => '?',
+                })
+                .collect();
+            write!(stdout, "|{line}|\r\n")?;
+        }
+        write!(stdout, "+{border}+\r\n")?;
+        stdout.flush()?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+//! I2C address of the backpack.
+
+use core::fmt;
+
+use embedded_hal::i2c::I2c;
+
+/// 7-bit I2C address of a PCF8574 (0x20–0x27) or PCF8574A (0x38–0x3F) backpack.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Address(u8);
+
+/// Returned when an address is outside the PCF8574 and PCF8574A ranges.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct InvalidAddress(pub u8);
+
+impl Address {
+    /// Default address of PCF8574 backpacks, with A0–A2 left open
+    pub const PCF8574: Address = Address(0x27);
+    /// Default address of PCF8574A backpacks, with A0–A2 left open
+    pub const PCF8574A: Address = Address(0x3F);
+    /// Address of PCF8575 boards with A0–A2 tied low, as on most of them
+    pub const PCF8575: Address = Address(0x20);
+    /// Default address of MCP23008 backpacks, with A0–A2 left open
+    pub const MCP23008: Address = Address(0x20);
+    /// Default address of MCP23017 RGB LCD shields, with A0–A2 left open
+    pub const MCP23017: Address = Address(0x20);
+
+    /// Every PCF8574 and PCF8574A address, the defaults first, in the order
+    /// `Lcd::new_autodetect` probes them
+    pub const ALL: [Address; 16] = [
+        Address(0x27),
+        Address(0x3F),
+        Address(0x20),
+        Address(0x21),
+        Address(0x22),
+        Address(0x23),
+        Address(0x24),
+        Address(0x25),
+        Address(0x26),
+        Address(0x38),
+        Address(0x39),
+        Address(0x3A),
+        Address(0x3B),
+        Address(0x3C),
+        Address(0x3D),
+        Address(0x3E),
+    ];
+
+    /// Check that `address` is one a PCF8574 or PCF8574A can be configured with.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidAddress` if the address is outside both ranges.
+    pub const fn new(address: u8) -> Result<Self, InvalidAddress> {
+        match address {
+            0x20..=0x27 | 0x38..=0x3F => Ok(Address(address)),
+            _ => Err(InvalidAddress(address)),
+        }
+    }
+
+    /// Use `address` as is, for bridges that are not PCF8574 based.
+    pub const fn unchecked(address: u8) -> Self {
+        Address(address)
+    }
+
+    /// The 7-bit address
+    pub const fn value(&self) -> u8 {
+        self.0
+    }
+}
+
+/// Addresses among `candidates` that answer, e.g. `Address::ALL`, in order.
+///
+/// Each address is probed when the iterator gets to it, by reading a byte,
+/// which does not change the outputs of a PCF8574. Any other device answering
+/// in the same ranges is returned too.
+pub fn scan<'a, I2C: I2c>(
+    i2c: &'a mut I2C,
+    candidates: &'a [Address],
+) -> impl Iterator<Item = Address> + 'a {
+    candidates
+        .iter()
+        .copied()
+        .filter(move |address| i2c.read(address.value(), &mut [0]).is_ok())
+}
+
+impl TryFrom<u8> for Address {
+    type Error = InvalidAddress;
+
+    fn try_from(address: u8) -> Result<Self, Self::Error> {
+        Self::new(address)
+    }
+}
+
+impl From<Address> for u8 {
+    fn from(address: Address) -> Self {
+        address.0
+    }
+}
+
+impl fmt::Display for InvalidAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:#04x} is not a PCF8574 (0x20-0x27) or PCF8574A (0x38-0x3F) address",
+            self.0
+        )
+    }
+}
+
+impl core::error::Error for InvalidAddress {}
@@ -0,0 +1,236 @@
+//! Double-height text built from custom segment characters.
+
+use crate::{Error, Lcd, LcdBus, RomVariant, WaitStrategy};
+
+// Segments, uploaded to CGRAM when a text needs them
+const LT: u8 = 0; // upper left corner
+const UB: u8 = 1; // upper bar
+const RT: u8 = 2; // upper right corner
+const LL: u8 = 3; // lower left corner
+const LB: u8 = 4; // lower bar
+const LR: u8 = 5; // lower right corner
+const UMB: u8 = 6; // upper and middle bars
+const LMB: u8 = 7; // middle and lower bars
+const F: u8 = 8; // full block, taken from the ROM when it has one
+
+// Characters of the ROM
+const FULL_BLOCK: u8 = 0xff; // only on the A00 ROM, `ÿ` on the A02 one
+const SP: u8 = b' ';
+
+const SEGMENTS: [[u8; 8]; 9] = [
+    [0x07, 0x0f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f],
+    [0x1f, 0x1f, 0x1f, 0x00, 0x00, 0x00, 0x00, 0x00],
+    [0x1c, 0x1e, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f],
+    [0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x0f, 0x07],
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x1f, 0x1f, 0x1f],
+    [0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1e, 0x1c],
+    [0x1f, 0x1f, 0x1f, 0x00, 0x00, 0x00, 0x1f, 0x1f],
+    [0x1f, 0x00, 0x00, 0x00, 0x00, 0x1f, 0x1f, 0x1f],
+    [0x1f; 8],
+];
+
+/// Upper and lower halves of a character
+type BigChar = (&'static [u8], &'static [u8]);
+
+/// Renders digits, A–Z and common punctuation two rows tall, three columns
+/// wide for most characters.
+///
+/// The segment characters the font is made of are uploaded to CGRAM on the
+/// fly, only when a text needs them and is not already resident. The full
+/// block is one of them on displays with the A02 ROM, which has none, see
+/// `Lcd::set_rom_variant`. By default every CGRAM location may be claimed, use
+/// `with_slots` to keep some for other custom characters.
+#[derive(Copy, Clone, Debug)]
+pub struct BigFont {
+    slots: u8,
+}
+
+impl BigFont {
+    /// A font that may claim every CGRAM location
+    pub const fn new() -> Self {
+        BigFont { slots: 0xff }
+    }
+
+    /// A font that may only claim the CGRAM locations set in the `slots` mask,
+    /// bit 0 being location 0
+    pub const fn with_slots(slots: u8) -> Self {
+        BigFont { slots }
+    }
+
+    /**
+    Print `text` with its upper half on `row` and its lower half on the next
+    row, starting at `col`. Lowercase letters are shown as uppercase, and
+    characters the font does not know as `?`. Text past the last column is
+    clipped, and the cursor is moved back to where it was before the call.
+
+    Returns the number of columns the text takes.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::CgramFull` if the text needs more segments than there are slots
+    the font may claim.
+    */
+    pub fn print<B: LcdBus, D: WaitStrategy>(
+        &self,
+        lcd: &mut Lcd<B, D>,
+        col: u8,
+        row: u8,
+        text: &str,
+    ) -> Result<u8, Error<B::Error>> {
+        let slots = self.load_segments(lcd, text)?;
+        let rom = lcd.charset.rom;
+        let resolve = |code: u8| {
+            if in_rom(rom, code) {
+                FULL_BLOCK
+            } else if (code as usize) < SEGMENTS.len() {
+                slots[code as usize]
+            } else {
+                code
+            }
+        };
+
+        let (prev_col, prev_row) = lcd.position;
+        let mut width = 0;
+
+        for (half, row) in [row, row + 1].into_iter().enumerate() {
+            if row >= lcd.rows {
+                break;
+            }
+            lcd.set_cursor_position(col, row)?;
+
+            let mut x = col;
+            for (i, c) in text.chars().enumerate() {
+                let (top, bottom) = big_char(c);
+                let segments = if half == 0 { top } else { bottom };
+
+                // One blank column between characters
+                let gap = if i > 0 { Some(SP) } else { None };
+                for code in gap.into_iter().chain(segments.iter().copied()) {
+                    if x < lcd.cols {
+                        lcd.write(resolve(code))?;
+                    }
+                    x = x.saturating_add(1);
+                }
+            }
+            width = x - col;
+        }
+
+        lcd.set_cursor_position(prev_col, prev_row)?;
+        Ok(width)
+    }
+
+    // Make sure every segment used by `text` is in CGRAM, returning the
+    // location of each segment
+    fn load_segments<B: LcdBus, D: WaitStrategy>(
+        &self,
+        lcd: &mut Lcd<B, D>,
+        text: &str,
+    ) -> Result<[u8; 9], Error<B::Error>> {
+        let mut needed = 0u16;
+        for c in text.chars() {
+            let (top, bottom) = big_char(c);
+            for code in top.iter().chain(bottom) {
+                if (*code as usize) < SEGMENTS.len() && !in_rom(lcd.charset.rom, *code) {
+                    needed |= 1 << code;
+                }
+            }
+        }
+
+        let mut locations = [0; 9];
+        let mut missing = 0u16;
+        for (segment, charmap) in SEGMENTS.iter().enumerate() {
+            if needed & (1 << segment) == 0 {
+                continue;
+            }
+            match lcd.glyphs.iter().position(|glyph| *glyph == Some(*charmap)) {
+                Some(location) => locations[segment] = location as u8,
+                None => missing |= 1 << segment,
+            }
+        }
+
+        // Slots holding a segment this text uses must not be overwritten
+        let mut taken = 0u8;
+        for (segment, location) in locations.iter().enumerate() {
+            if needed & !missing & (1 << segment) != 0 {
+                taken |= 1 << location;
+            }
+        }
+
+        for (segment, charmap) in SEGMENTS.iter().enumerate() {
+            if missing & (1 << segment) == 0 {
+                continue;
+            }
+            let free = self.slots & !taken;
+            // Prefer locations nothing was uploaded to
+            let location = (0..8u8)
+                .filter(|location| free & (1 << location) != 0)
+                .min_by_key(|location| lcd.glyphs[*location as usize].is_some())
+                .ok_or(Error::CgramFull)?;
+
+            lcd.upload_char(location, *charmap)?;
+            locations[segment] = location;
+            taken |= 1 << location;
+        }
+
+        Ok(locations)
+    }
+}
+
+impl Default for BigFont {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Whether the segment `code` is a glyph of the ROM
+fn in_rom(rom: RomVariant, code: u8) -> bool {
+    code == F && rom == RomVariant::A00
+}
+
+fn big_char(c: char) -> BigChar {
+    match c.to_ascii_uppercase() {
+        '0' | 'O' => (&[LT, UB, RT], &[LL, LB, LR]),
+        '1' => (&[UB, RT, SP], &[LB, F, LB]),
+        '2' => (&[UMB, UMB, RT], &[LL, LB, LB]),
+        '3' => (&[UMB, UMB, RT], &[LB, LB, LR]),
+        '4' => (&[LL, LB, F], &[SP, SP, F]),
+        '5' => (&[F, UMB, UMB], &[LB, LB, LR]),
+        '6' => (&[LT, UMB, UMB], &[LL, LB, LR]),
+        '7' => (&[UB, UB, RT], &[SP, F, SP]),
+        '8' => (&[LT, UMB, RT], &[LL, LB, LR]),
+        '9' => (&[LT, UMB, RT], &[SP, SP, F]),
+        'A' => (&[LT, UMB, RT], &[F, SP, F]),
+        'B' => (&[F, UMB, RT], &[F, LMB, LR]),
+        'C' => (&[LT, UB, UB], &[LL, LB, LB]),
+        'D' => (&[F, UB, RT], &[F, LB, LR]),
+        'E' => (&[F, UMB, UMB], &[F, LMB, LMB]),
+        'F' => (&[F, UMB, UMB], &[F, SP, SP]),
+        'G' => (&[LT, UB, UB], &[LL, LB, F]),
+        'H' => (&[F, LB, F], &[F, UB, F]),
+        'I' => (&[UB, F, UB], &[LB, F, LB]),
+        'J' => (&[SP, SP, F], &[LB, LB, LR]),
+        'K' => (&[F, LB, LR], &[F, UB, RT]),
+        'L' => (&[F, SP, SP], &[F, LB, LB]),
+        'M' => (&[LT, UB, F, UB, RT], &[F, SP, F, SP, F]),
+        'N' => (&[F, UB, RT], &[F, SP, F]),
+        'P' => (&[F, UMB, RT], &[F, SP, SP]),
+        'Q' => (&[LT, UB, RT, SP], &[LL, LB, F, LB]),
+        'R' => (&[F, UMB, RT], &[F, SP, RT]),
+        'S' => (&[LT, UMB, UMB], &[LMB, LMB, LR]),
+        'T' => (&[UB, F, UB], &[SP, F, SP]),
+        'U' => (&[F, SP, F], &[LL, LB, LR]),
+        'V' => (&[F, SP, F], &[LL, F, LR]),
+        'W' => (&[F, SP, F, SP, F], &[LL, LB, F, LB, LR]),
+        'X' => (&[LL, LB, LR], &[LT, UB, RT]),
+        'Y' => (&[LL, LB, LR], &[SP, F, SP]),
+        'Z' => (&[UB, UB, LR], &[LT, LB, LB]),
+        ' ' => (&[SP, SP], &[SP, SP]),
+        '.' => (&[SP], &[LB]),
+        ':' => (&[LB], &[LB]),
+        '!' => (&[F], &[LB]),
+        '-' => (&[LB, LB], &[SP, SP]),
+        '+' => (&[LB, F, LB], &[UB, F, UB]),
+        _ => (&[UB, UMB, RT], &[SP, LB, SP]),
+    }
+}
@@ -0,0 +1,136 @@
+//! Custom characters sliced out of 1-bpp bitmaps.
+
+use crate::{Error, Lcd, LcdBus, WaitStrategy};
+
+/// Width of a character, in pixels
+const CHAR_WIDTH: u16 = 5;
+/// Height of a character, in pixels
+const CHAR_HEIGHT: u16 = 8;
+
+/// A packed 1-bpp image, sliced into 5×8 custom characters.
+///
+/// Pixels are stored row by row, most significant bit first, each row padded
+/// to a whole byte, with set bits being lit pixels. This is the layout of
+/// `embedded-graphics` `ImageRaw<BinaryColor>` data, and of the pixel array of
+/// monochrome images exported by most editors, so icons can be used straight
+/// from `include_bytes!`. Pixels past the image are off.
+#[derive(Copy, Clone, Debug)]
+pub struct Bitmap<'a> {
+    data: &'a [u8],
+    width: u16,
+    height: u16,
+}
+
+impl<'a> Bitmap<'a> {
+    /// An image of `width` × `height` pixels. Returns `None` if `data` is too
+    /// short for that size.
+    pub const fn new(data: &'a [u8], width: u16, height: u16) -> Option<Self> {
+        let stride = width.div_ceil(8) as usize;
+        if data.len() < stride * height as usize {
+            return None;
+        }
+        Some(Bitmap {
+            data,
+            width,
+            height,
+        })
+    }
+
+    /// Number of characters the image spans horizontally
+    pub fn cols(&self) -> u16 {
+        self.width.div_ceil(CHAR_WIDTH)
+    }
+
+    /// Number of characters the image spans vertically
+    pub fn rows(&self) -> u16 {
+        self.height.div_ceil(CHAR_HEIGHT)
+    }
+
+    /// Whether the pixel at `x`, `y` is lit
+    pub fn pixel(&self, x: u16, y: u16) -> bool {
+        if x >= self.width || y >= self.height {
+            return false;
+        }
+        let stride = self.width.div_ceil(8) as usize;
+        let byte = self.data[y as usize * stride + x as usize / 8];
+        byte & (0x80 >> (x % 8)) != 0
+    }
+
+    /// Charmap of the character at `col`, `row` of the image, in characters
+    pub fn glyph(&self, col: u16, row: u16) -> [u8; 8] {
+        let mut charmap = [0; 8];
+        for (line, bits) in charmap.iter_mut().enumerate() {
+            let y = row * CHAR_HEIGHT + line as u16;
+            for x in 0..CHAR_WIDTH {
+                if self.pixel(col * CHAR_WIDTH + x, y) {
+                    *bits |= 0x10 >> x;
+                }
+            }
+        }
+        charmap
+    }
+
+    /**
+    Upload the characters of the image, row by row, to the CGRAM locations
+    starting at `first_location`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::CgramFull` if the image needs locations past the eighth one.
+    Nothing is uploaded in that case.
+    */
+    pub fn load<B: LcdBus, D: WaitStrategy>(
+        &self,
+        lcd: &mut Lcd<B, D>,
+        first_location: u8,
+    ) -> Result<(), Error<B::Error>> {
+        let size = self.cols() as usize * self.rows() as usize;
+        if first_location as usize + size > 8 {
+            return Err(Error::CgramFull);
+        }
+
+        let mut location = first_location;
+        for row in 0..self.rows() {
+            for col in 0..self.cols() {
+                lcd.upload_char(location, self.glyph(col, row))?;
+                location += 1;
+            }
+        }
+        Ok(())
+    }
+
+    /**
+    Show the image uploaded by `load` with its top left corner at `col`, `row`,
+    clipped to the display. The cursor is moved back to where it was before
+    the call.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn show<B: LcdBus, D: WaitStrategy>(
+        &self,
+        lcd: &mut Lcd<B, D>,
+        col: u8,
+        row: u8,
+        first_location: u8,
+    ) -> Result<(), Error<B::Error>> {
+        let (prev_col, prev_row) = lcd.position;
+        let mut location = first_location;
+        for y in 0..self.rows() {
+            let row = row as u16 + y;
+            if row >= lcd.rows as u16 {
+                break;
+            }
+            lcd.set_cursor_position(col, row as u8)?;
+            for x in 0..self.cols() {
+                if col as u16 + x < lcd.cols as u16 {
+                    lcd.write(location)?;
+                }
+                location = location.wrapping_add(1);
+            }
+        }
+        lcd.set_cursor_position(prev_col, prev_row)
+    }
+}
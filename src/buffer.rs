@@ -0,0 +1,100 @@
+//! Shadow copy of the characters shown on the display.
+
+/// Widest line a HD44780 can show
+pub(crate) const MAX_COLS: usize = 40;
+/// Highest row count supported by the driver
+pub(crate) const MAX_ROWS: usize = 4;
+
+/// Keeps the ROM code of every visible cell, and which cells still have to be
+/// sent to the display.
+pub(crate) struct ShadowBuffer {
+    cells: [[u8; MAX_COLS]; MAX_ROWS],
+    dirty: [u64; MAX_ROWS],
+}
+
+impl ShadowBuffer {
+    pub(crate) const fn new() -> Self {
+        ShadowBuffer {
+            cells: [[b' '; MAX_COLS]; MAX_ROWS],
+            dirty: [0; MAX_ROWS],
+        }
+    }
+
+    /// Code stored at the given cell, if the cell exists
+    pub(crate) fn get(&self, col: u8, row: u8) -> Option<u8> {
+        self.cells
+            .get(row as usize)
+            .and_then(|cells| cells.get(col as usize))
+            .copied()
+    }
+
+    /// Cells of the given row
+    pub(crate) fn row(&self, row: u8) -> Option<&[u8; MAX_COLS]> {
+        self.cells.get(row as usize)
+    }
+
+    /// Record a value that has already been sent to the display
+    pub(crate) fn store(&mut self, col: u8, row: u8, value: u8) {
+        if let Some(cell) = self.cell_mut(col, row) {
+            *cell = value;
+            self.dirty[row as usize] &= !(1 << col);
+        }
+    }
+
+    /// Record a value that still has to be sent to the display
+    pub(crate) fn stage(&mut self, col: u8, row: u8, value: u8) {
+        if let Some(cell) = self.cell_mut(col, row) {
+            if *cell != value {
+                *cell = value;
+                self.dirty[row as usize] |= 1 << col;
+            }
+        }
+    }
+
+    /// Value of the cell if it still has to be sent to the display
+    pub(crate) fn dirty(&self, col: u8, row: u8) -> Option<u8> {
+        let value = self.get(col, row)?;
+        if self.dirty[row as usize] & (1 << col) != 0 {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Mark a cell as matching the display
+    pub(crate) fn mark_clean(&mut self, col: u8, row: u8) {
+        if self.get(col, row).is_some() {
+            self.dirty[row as usize] &= !(1 << col);
+        }
+    }
+
+    /// Mark every cell as still to be sent, e.g. after the display lost its content
+    pub(crate) fn invalidate(&mut self) {
+        self.dirty = [u64::MAX; MAX_ROWS];
+    }
+
+    /// Fill the buffer with spaces, as the display does on CLEARDISPLAY
+    pub(crate) fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// FNV-1a hash of the first `cols` cells of the first `rows` rows
+    pub(crate) fn hash(&self, cols: u8, rows: u8) -> u32 {
+        const OFFSET_BASIS: u32 = 0x811c_9dc5;
+        const PRIME: u32 = 0x0100_0193;
+
+        self.cells
+            .iter()
+            .take(rows as usize)
+            .flat_map(|cells| cells.iter().take(cols as usize))
+            .fold(OFFSET_BASIS, |hash, code| {
+                (hash ^ *code as u32).wrapping_mul(PRIME)
+            })
+    }
+
+    fn cell_mut(&mut self, col: u8, row: u8) -> Option<&mut u8> {
+        self.cells
+            .get_mut(row as usize)
+            .and_then(|cells| cells.get_mut(col as usize))
+    }
+}
@@ -0,0 +1,115 @@
+//! Transport between the driver and the controller of the display.
+
+use crate::{Backlight, BitMode, WaitStrategy};
+
+/// Register of the controller a byte goes to or comes from, selected by RS
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Register {
+    /// Instructions, or the busy flag and address counter when reading
+    Command,
+    /// DDRAM or CGRAM, at the address counter
+    Data,
+}
+
+/// Controllers the next transfers go to, on displays made of two of them,
+/// such as 40x4 ones, each with its own enable line
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Controllers {
+    First,
+    Second,
+    Both,
+}
+
+/// Moves bytes between the driver and a HD44780 compatible controller.
+///
+/// `Lcd` implements the commands of the controller on top of these methods, so
+/// a display wired in another way than through the PCF8574 of an I2C
+/// backpack, see `Pcf8574`, only needs its transport implemented to be driven
+/// with the whole API, using `Lcd::with_bus`.
+pub trait LcdBus {
+    type Error: core::fmt::Debug;
+
+    /// Send the high nibble of `value` alone, as done during initialization,
+    /// while the controller may still be in 8-bit mode. 8-bit buses send it
+    /// with D0–D3 low.
+    fn write_nibble(
+        &mut self,
+        value: u8,
+        register: Register,
+        wait: &mut impl WaitStrategy,
+    ) -> Result<(), Self::Error>;
+
+    /// Send a whole byte
+    fn write_byte(
+        &mut self,
+        value: u8,
+        register: Register,
+        wait: &mut impl WaitStrategy,
+    ) -> Result<(), Self::Error>;
+
+    /// Turn the backlight on or off, if the bus drives it
+    fn set_backlight(&mut self, backlight: Backlight) -> Result<(), Self::Error>;
+
+    /// Check that the device still answers, by writing something the display
+    /// ignores. Sets the backlight again by default.
+    fn check(&mut self, backlight: Backlight) -> Result<(), Self::Error> {
+        self.set_backlight(backlight)
+    }
+
+    /// Read a byte, or `None` if the bus cannot read from the controller, e.g.
+    /// because R/W is tied low. Does not by default.
+    fn read_byte(
+        &mut self,
+        _register: Register,
+        _wait: &mut impl WaitStrategy,
+    ) -> Result<Option<u8>, Self::Error> {
+        Ok(None)
+    }
+
+    /// Setup specific to the controller, run during initialization once the
+    /// interface is in its mode, before the function set. Nothing by default.
+    fn setup(&mut self, _wait: &mut impl WaitStrategy) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Learn the function set command the driver sends, with the width, lines
+    /// and font it was set up for, before `setup` and each function set, for
+    /// controllers that send it along with their own commands. Ignored by
+    /// default.
+    fn set_function(&mut self, _function: u8) {}
+
+    /// Width of the data bus, which the controller is initialized for, 4-bit
+    /// by default
+    fn bit_mode(&self) -> BitMode {
+        BitMode::Bit4
+    }
+
+    /// Number of controllers behind the bus, 1 by default
+    fn controllers(&self) -> u8 {
+        1
+    }
+
+    /// Send the next transfers to `controllers`. Only called with
+    /// `Controllers::Second` when there are two of them.
+    fn select(&mut self, _controllers: Controllers) {}
+
+    /// Set the contrast, from 0 to 255, when restoring saved settings, on
+    /// controllers having a setting for it, returning whether they do. They
+    /// do not by default.
+    fn restore_contrast(&mut self, _level: u8) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+
+    /// Bytes written on the bus for a command or a character, at most, which
+    /// `Lcd::flush_chunk` budgets with. 1 by default, so buses that do not
+    /// count bytes budget in commands.
+    fn command_bytes(&self) -> u32 {
+        1
+    }
+
+    /// Bytes written on the bus so far, wrapping, reported in `FrameStats`, or
+    /// 0 if the bus does not count them
+    fn bytes_written(&self) -> u32 {
+        0
+    }
+}
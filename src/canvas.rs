@@ -0,0 +1,201 @@
+//! Pixel drawing area made of custom characters.
+
+use crate::{Error, Lcd, LcdBus, WaitStrategy};
+
+/// Width of a character, in pixels
+const CHAR_WIDTH: u8 = 5;
+/// Height of a character, in pixels
+const CHAR_HEIGHT: u8 = 8;
+
+/// A region of `width` × `height` characters shown as a grid of
+/// 5·`width` × 8·`height` pixels.
+///
+/// Every character of the region is a custom character, so the region can
+/// span at most the eight CGRAM locations. Drawing only changes the canvas in
+/// memory, `flush` uploads the characters that changed and places them on
+/// the display.
+#[derive(Clone, Debug)]
+pub struct Canvas {
+    col: u8,
+    row: u8,
+    width: u8,
+    height: u8,
+    first_location: u8,
+    cells: [[u8; 8]; 8],
+    // Cells whose character has to be uploaded again
+    dirty: u8,
+}
+
+impl Canvas {
+    /**
+    Claim the region of `width` × `height` characters whose top left corner is
+    at `col`, `row`, backed by the CGRAM locations starting at `first_location`.
+    Returns `None` if the region needs locations past the eighth one.
+    */
+    pub fn new(col: u8, row: u8, width: u8, height: u8, first_location: u8) -> Option<Self> {
+        let size = width as usize * height as usize;
+        if size == 0 || first_location as usize + size > 8 {
+            return None;
+        }
+
+        Some(Canvas {
+            col,
+            row,
+            width,
+            height,
+            first_location,
+            cells: [[0; 8]; 8],
+            dirty: 0xff,
+        })
+    }
+
+    /// Width of the canvas, in pixels
+    pub fn width(&self) -> u8 {
+        self.width * CHAR_WIDTH
+    }
+
+    /// Height of the canvas, in pixels
+    pub fn height(&self) -> u8 {
+        self.height * CHAR_HEIGHT
+    }
+
+    /// Turn the pixel at `x`, `y` on or off. Pixels outside the canvas are ignored.
+    pub fn set_pixel(&mut self, x: u8, y: u8, on: bool) {
+        if x >= self.width() || y >= self.height() {
+            return;
+        }
+
+        let cell = (y / CHAR_HEIGHT) * self.width + x / CHAR_WIDTH;
+        let bit = 1 << (CHAR_WIDTH - 1 - x % CHAR_WIDTH);
+        let line = &mut self.cells[cell as usize][(y % CHAR_HEIGHT) as usize];
+        let previous = *line;
+
+        if on {
+            *line |= bit;
+        } else {
+            *line &= !bit;
+        }
+        if *line != previous {
+            self.dirty |= 1 << cell;
+        }
+    }
+
+    /// Whether the pixel at `x`, `y` is on. Pixels outside the canvas are off.
+    pub fn pixel(&self, x: u8, y: u8) -> bool {
+        if x >= self.width() || y >= self.height() {
+            return false;
+        }
+
+        let cell = (y / CHAR_HEIGHT) * self.width + x / CHAR_WIDTH;
+        let bit = 1 << (CHAR_WIDTH - 1 - x % CHAR_WIDTH);
+        self.cells[cell as usize][(y % CHAR_HEIGHT) as usize] & bit != 0
+    }
+
+    /// Draw a line between two pixels, both included
+    pub fn line(&mut self, x0: u8, y0: u8, x1: u8, y1: u8, on: bool) {
+        // Bresenham's line algorithm
+        let (mut x, mut y) = (x0 as i16, y0 as i16);
+        let (x1, y1) = (x1 as i16, y1 as i16);
+        let dx = (x1 - x).abs();
+        let dy = -(y1 - y).abs();
+        let sx = if x < x1 { 1 } else { -1 };
+        let sy = if y < y1 { 1 } else { -1 };
+        let mut error = dx + dy;
+
+        loop {
+            self.set_pixel(x as u8, y as u8, on);
+            if x == x1 && y == y1 {
+                break;
+            }
+            let doubled = 2 * error;
+            if doubled >= dy {
+                error += dy;
+                x += sx;
+            }
+            if doubled <= dx {
+                error += dx;
+                y += sy;
+            }
+        }
+    }
+
+    /// Turn every pixel off
+    pub fn clear(&mut self) {
+        for (cell, lines) in self.cells.iter_mut().enumerate() {
+            if lines.iter().any(|line| *line != 0) {
+                *lines = [0; 8];
+                self.dirty |= 1 << cell;
+            }
+        }
+    }
+
+    /**
+    Upload the characters that changed since the last flush, and show the
+    canvas in its region. The cursor is moved back to where it was before the call.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn flush<B: LcdBus, D: WaitStrategy>(
+        &mut self,
+        lcd: &mut Lcd<B, D>,
+    ) -> Result<(), Error<B::Error>> {
+        let size = self.width * self.height;
+        for cell in 0..size {
+            if self.dirty & (1 << cell) != 0 {
+                lcd.upload_char(self.first_location + cell, self.cells[cell as usize])?;
+                self.dirty &= !(1 << cell);
+            }
+        }
+
+        let (prev_col, prev_row) = lcd.position;
+        for y in 0..self.height {
+            let row = self.row.saturating_add(y);
+            if row >= lcd.rows {
+                break;
+            }
+            lcd.set_cursor_position(self.col, row)?;
+            for x in 0..self.width {
+                lcd.write(self.first_location + y * self.width + x)?;
+            }
+        }
+        lcd.set_cursor_position(prev_col, prev_row)
+    }
+}
+
+#[cfg(feature = "embedded-graphics")]
+impl embedded_graphics_core::geometry::OriginDimensions for Canvas {
+    fn size(&self) -> embedded_graphics_core::geometry::Size {
+        embedded_graphics_core::geometry::Size::new(self.width() as u32, self.height() as u32)
+    }
+}
+
+#[cfg(feature = "embedded-graphics")]
+impl embedded_graphics_core::draw_target::DrawTarget for Canvas {
+    type Color = embedded_graphics_core::pixelcolor::BinaryColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = embedded_graphics_core::Pixel<Self::Color>>,
+    {
+        for embedded_graphics_core::Pixel(point, color) in pixels {
+            if let (Ok(x), Ok(y)) = (u8::try_from(point.x), u8::try_from(point.y)) {
+                self.set_pixel(x, y, color.is_on());
+            }
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        if color.is_on() {
+            for y in 0..self.height() {
+                self.line(0, y, self.width() - 1, y, true);
+            }
+        } else {
+            Canvas::clear(self);
+        }
+        Ok(())
+    }
+}
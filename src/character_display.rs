@@ -0,0 +1,83 @@
+//! Operations shared by every character display.
+
+use crate::{Backlight, Blink, Cursor, Display, Error, Lcd, LcdBus, WaitStrategy};
+
+/// High level operations of a character display, implemented by `Lcd` and by
+/// wrappers around it, so application code can drive any of them.
+pub trait CharacterDisplay {
+    type Error;
+
+    /// Clear the display and move the cursor home
+    fn clear(&mut self) -> Result<(), Self::Error>;
+
+    /// Move the cursor home
+    fn home(&mut self) -> Result<(), Self::Error>;
+
+    /// Move the cursor to the given position
+    fn set_cursor_position(&mut self, col: u8, row: u8) -> Result<(), Self::Error>;
+
+    /// Print a string at the cursor position
+    fn print(&mut self, s: &str) -> Result<(), Self::Error>;
+
+    /// Write the character with the given ROM code at the cursor position
+    fn write(&mut self, value: u8) -> Result<(), Self::Error>;
+
+    /// Upload a custom character to CGRAM location (0 - 7)
+    fn create_char(&mut self, location: u8, charmap: [u8; 8]) -> Result<(), Self::Error>;
+
+    /// Turn the display on or off
+    fn set_display(&mut self, display: Display) -> Result<(), Self::Error>;
+
+    /// Show or hide the non-blinking cursor
+    fn set_cursor(&mut self, cursor: Cursor) -> Result<(), Self::Error>;
+
+    /// Show or hide the blinking block cursor
+    fn set_blink(&mut self, blink: Blink) -> Result<(), Self::Error>;
+
+    /// Turn the backlight on or off
+    fn set_backlight(&mut self, backlight: Backlight) -> Result<(), Self::Error>;
+}
+
+impl<B: LcdBus, D: WaitStrategy> CharacterDisplay for Lcd<B, D> {
+    type Error = Error<B::Error>;
+
+    fn clear(&mut self) -> Result<(), Self::Error> {
+        Lcd::clear(self)
+    }
+
+    fn home(&mut self) -> Result<(), Self::Error> {
+        Lcd::home(self)
+    }
+
+    fn set_cursor_position(&mut self, col: u8, row: u8) -> Result<(), Self::Error> {
+        Lcd::set_cursor_position(self, col, row)
+    }
+
+    fn print(&mut self, s: &str) -> Result<(), Self::Error> {
+        Lcd::print(self, s)
+    }
+
+    fn write(&mut self, value: u8) -> Result<(), Self::Error> {
+        Lcd::write(self, value)
+    }
+
+    fn create_char(&mut self, location: u8, charmap: [u8; 8]) -> Result<(), Self::Error> {
+        Lcd::create_char(self, location, charmap)
+    }
+
+    fn set_display(&mut self, display: Display) -> Result<(), Self::Error> {
+        Lcd::set_display(self, display)
+    }
+
+    fn set_cursor(&mut self, cursor: Cursor) -> Result<(), Self::Error> {
+        Lcd::set_cursor(self, cursor)
+    }
+
+    fn set_blink(&mut self, blink: Blink) -> Result<(), Self::Error> {
+        Lcd::set_blink(self, blink)
+    }
+
+    fn set_backlight(&mut self, backlight: Backlight) -> Result<(), Self::Error> {
+        Lcd::set_backlight(self, backlight)
+    }
+}
@@ -0,0 +1,118 @@
+//! Mapping between chars and the codes of the character ROM.
+
+/// Character ROM of the controller, which sets the glyphs above ASCII
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RomVariant {
+    /// Japanese ROM, the most common one: katakana and a few Greek letters and
+    /// symbols, with `¥` instead of `\` and arrows instead of `~` and DEL
+    A00,
+    /// European ROM: full ASCII and most of Latin-1
+    A02,
+}
+
+/// Chars of the A00 ROM outside of ASCII and katakana
+const A00_SYMBOLS: [(char, u8); 24] = [
+    ('¥', 0x5c),
+    ('→', 0x7e),
+    ('←', 0x7f),
+    ('・', 0xa5),
+    ('°', 0xdf),
+    ('α', 0xe0),
+    ('ä', 0xe1),
+    ('β', 0xe2),
+    ('ε', 0xe3),
+    ('μ', 0xe4),
+    ('σ', 0xe5),
+    ('ρ', 0xe6),
+    ('√', 0xe8),
+    ('¢', 0xec),
+    ('ñ', 0xee),
+    ('ö', 0xef),
+    ('θ', 0xf2),
+    ('∞', 0xf3),
+    ('Ω', 0xf4),
+    ('ü', 0xf5),
+    ('Σ', 0xf6),
+    ('π', 0xf7),
+    ('÷', 0xfd),
+    ('█', 0xff),
+];
+
+/// First half-width katakana, `｡`, at 0xa1 in the A00 ROM
+const KATAKANA: u32 = 0xff61;
+
+/// Chars of the A02 ROM outside of ASCII and Latin-1
+const A02_SYMBOLS: [(char, u8); 1] = [('⌂', 0x7f)];
+
+/// How chars are turned into ROM codes and back
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Charset {
+    pub(crate) rom: RomVariant,
+    /// Code printed for chars the ROM does not have
+    pub(crate) replacement: u8,
+    /// Char shown by each custom character, if any
+    pub(crate) custom: [Option<char>; 8],
+}
+
+impl Charset {
+    pub(crate) const fn new() -> Self {
+        Charset {
+            rom: RomVariant::A00,
+            replacement: b'?',
+            custom: [None; 8],
+        }
+    }
+
+    /// Code of the glyph showing `c`, or the replacement code. Chars `\0` to
+    /// `\x07` are the custom characters, as are the chars registered with them.
+    pub(crate) fn encode(&self, c: char) -> u8 {
+        if let Some(location) = self.custom.iter().position(|custom| *custom == Some(c)) {
+            return location as u8;
+        }
+        self.rom_code(c).unwrap_or(self.replacement)
+    }
+
+    /// Char shown by the glyph at `code`, `\u{FFFD}` if it has no equivalent
+    pub(crate) fn decode(&self, code: u8) -> char {
+        if let Some(Some(c)) = self.custom.get(code as usize) {
+            return *c;
+        }
+
+        let symbols: &[(char, u8)] = match self.rom {
+            RomVariant::A00 => &A00_SYMBOLS,
+            RomVariant::A02 => &A02_SYMBOLS,
+        };
+        if let Some((c, _)) = symbols.iter().find(|(_, symbol)| *symbol == code) {
+            return *c;
+        }
+
+        match (self.rom, code) {
+            (_, 0x00..=0x07 | 0x20..=0x7d) | (RomVariant::A02, 0x7e | 0xa0..=0xff) => code as char,
+            (RomVariant::A00, 0xa1..=0xdf) => char::from_u32(KATAKANA + (code - 0xa1) as u32)
+                .unwrap_or(char::REPLACEMENT_CHARACTER),
+            _ => char::REPLACEMENT_CHARACTER,
+        }
+    }
+
+    fn rom_code(&self, c: char) -> Option<u8> {
+        let symbols: &[(char, u8)] = match self.rom {
+            RomVariant::A00 => &A00_SYMBOLS,
+            RomVariant::A02 => &A02_SYMBOLS,
+        };
+        if let Some((_, code)) = symbols.iter().find(|(symbol, _)| *symbol == c) {
+            return Some(*code);
+        }
+
+        let code = c as u32;
+        match (self.rom, code) {
+            (RomVariant::A00, 0x5c) => None,
+            (_, 0x00..=0x07 | 0x20..=0x7d) | (RomVariant::A02, 0x7e | 0xa0..=0xff) => {
+                Some(code as u8)
+            }
+            (RomVariant::A00, _) if (KATAKANA..=KATAKANA + 0x3e).contains(&code) => {
+                Some((code - KATAKANA) as u8 + 0xa1)
+            }
+            _ => None,
+        }
+    }
+}
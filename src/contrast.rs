@@ -0,0 +1,273 @@
+//! Software contrast through a digital potentiometer driving V0.
+
+use core::ops::{Deref, DerefMut};
+
+use embedded_hal::{delay::DelayNs, digital::OutputPin, i2c::I2c};
+
+use crate::{Backlight, Blink, CharacterDisplay, Cursor, Display};
+
+/// Something that can set the contrast of a display
+pub trait Contrast {
+    type Error;
+
+    /// Set the contrast level, from 0 to 255.
+    ///
+    /// For potentiometers the level is the wiper position, 0 being terminal B.
+    /// Whether that means more or less contrast depends on how V0 is wired.
+    fn set_contrast(&mut self, level: u8) -> Result<(), Self::Error>;
+}
+
+// Scale a 0-255 level to a 0-`max` wiper position
+fn wiper(level: u8, max: u16) -> u16 {
+    (level as u16 * max + 127) / 255
+}
+
+/// MCP4017/MCP4018/MCP4019 I2C potentiometer, 128 steps
+pub struct Mcp4018<I2C> {
+    i2c: I2C,
+}
+
+impl<I2C: I2c> Mcp4018<I2C> {
+    /// Fixed I2C address of the MCP401x
+    pub const ADDRESS: u8 = 0x2F;
+
+    pub fn new(i2c: I2C) -> Self {
+        Mcp4018 { i2c }
+    }
+
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+}
+
+impl<I2C: I2c> Contrast for Mcp4018<I2C> {
+    type Error = I2C::Error;
+
+    fn set_contrast(&mut self, level: u8) -> Result<(), Self::Error> {
+        self.i2c.write(Self::ADDRESS, &[wiper(level, 127) as u8])
+    }
+}
+
+/// MCP4531 (and other MCP45x1) I2C potentiometer, 129 steps
+pub struct Mcp4531<I2C> {
+    i2c: I2C,
+    address: u8,
+}
+
+impl<I2C: I2c> Mcp4531<I2C> {
+    /// Address with all address pins tied low
+    pub const DEFAULT_ADDRESS: u8 = 0x28;
+
+    pub fn new(i2c: I2C, address: u8) -> Self {
+        Mcp4531 { i2c, address }
+    }
+
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+}
+
+impl<I2C: I2c> Contrast for Mcp4531<I2C> {
+    type Error = I2C::Error;
+
+    fn set_contrast(&mut self, level: u8) -> Result<(), Self::Error> {
+        // Write command to the volatile wiper 0 register, with the two
+        // high data bits in the command byte
+        let value = wiper(level, 128);
+        self.i2c
+            .write(self.address, &[(value >> 8) as u8 & 0x03, value as u8])
+    }
+}
+
+/// X9C102/X9C103/X9C104/X9C503 up/down potentiometer, 100 steps, driven by
+/// its chip select, increment and up/down pins.
+///
+/// The wiper position is not readable, so the first call to `set_contrast`
+/// moves it all the way down before going to the requested level. The chip
+/// stores the wiper position in its non-volatile memory at the end of each
+/// change, which it is rated for 100,000 times, and restores it on power-up.
+pub struct X9c10x<CS, INC, UD, D> {
+    cs: CS,
+    inc: INC,
+    ud: UD,
+    delay: D,
+    position: Option<u8>,
+}
+
+impl<CS, INC, UD, D> X9c10x<CS, INC, UD, D>
+where
+    CS: OutputPin,
+    INC: OutputPin<Error = CS::Error>,
+    UD: OutputPin<Error = CS::Error>,
+    D: DelayNs,
+{
+    /// Highest wiper position
+    const MAX: u8 = 99;
+
+    pub fn new(cs: CS, inc: INC, ud: UD, delay: D) -> Self {
+        X9c10x {
+            cs,
+            inc,
+            ud,
+            delay,
+            position: None,
+        }
+    }
+
+    pub fn release(self) -> (CS, INC, UD, D) {
+        (self.cs, self.inc, self.ud, self.delay)
+    }
+
+    fn step(&mut self, up: bool, count: u8) -> Result<(), CS::Error> {
+        if up {
+            self.ud.set_high()?;
+        } else {
+            self.ud.set_low()?;
+        }
+        self.inc.set_high()?;
+        self.cs.set_low()?;
+        self.delay.delay_us(1);
+
+        // The wiper moves on each falling edge of INC
+        for _ in 0..count {
+            self.inc.set_low()?;
+            self.delay.delay_us(1);
+            self.inc.set_high()?;
+            self.delay.delay_us(1);
+        }
+
+        // INC is high, so no further step is taken when deselecting
+        self.cs.set_high()
+    }
+}
+
+impl<CS, INC, UD, D> Contrast for X9c10x<CS, INC, UD, D>
+where
+    CS: OutputPin,
+    INC: OutputPin<Error = CS::Error>,
+    UD: OutputPin<Error = CS::Error>,
+    D: DelayNs,
+{
+    type Error = CS::Error;
+
+    fn set_contrast(&mut self, level: u8) -> Result<(), Self::Error> {
+        let target = wiper(level, Self::MAX as u16) as u8;
+        let position = match self.position {
+            Some(position) => position,
+            None => {
+                self.step(false, Self::MAX)?;
+                0
+            }
+        };
+
+        if target > position {
+            self.step(true, target - position)?;
+        } else if target < position {
+            self.step(false, position - target)?;
+        }
+        self.position = Some(target);
+
+        Ok(())
+    }
+}
+
+/// A display with its contrast driven by a `Contrast` implementation, such as
+/// a digital potentiometer on V0.
+///
+/// Dereferences to the display, so it can be used just as the display alone.
+pub struct WithContrast<L, C> {
+    display: L,
+    contrast: C,
+    level: Option<u8>,
+}
+
+impl<L, C: Contrast> WithContrast<L, C> {
+    pub fn new(display: L, contrast: C) -> Self {
+        WithContrast {
+            display,
+            contrast,
+            level: None,
+        }
+    }
+
+    /**
+    Set the contrast level, from 0 to 255.
+
+    # Errors
+
+    Returns a `Result` that will report errors of the contrast control, if any.
+    */
+    pub fn set_contrast(&mut self, level: u8) -> Result<(), C::Error> {
+        self.contrast.set_contrast(level)?;
+        self.level = Some(level);
+        Ok(())
+    }
+
+    /// Last contrast level set, if any
+    pub fn contrast(&self) -> Option<u8> {
+        self.level
+    }
+
+    /// Give back the display and the contrast control
+    pub fn release(self) -> (L, C) {
+        (self.display, self.contrast)
+    }
+}
+
+impl<L, C> Deref for WithContrast<L, C> {
+    type Target = L;
+
+    fn deref(&self) -> &L {
+        &self.display
+    }
+}
+
+impl<L, C> DerefMut for WithContrast<L, C> {
+    fn deref_mut(&mut self) -> &mut L {
+        &mut self.display
+    }
+}
+
+impl<L: CharacterDisplay, C> CharacterDisplay for WithContrast<L, C> {
+    type Error = L::Error;
+
+    fn clear(&mut self) -> Result<(), Self::Error> {
+        self.display.clear()
+    }
+
+    fn home(&mut self) -> Result<(), Self::Error> {
+        self.display.home()
+    }
+
+    fn set_cursor_position(&mut self, col: u8, row: u8) -> Result<(), Self::Error> {
+        self.display.set_cursor_position(col, row)
+    }
+
+    fn print(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.display.print(s)
+    }
+
+    fn write(&mut self, value: u8) -> Result<(), Self::Error> {
+        self.display.write(value)
+    }
+
+    fn create_char(&mut self, location: u8, charmap: [u8; 8]) -> Result<(), Self::Error> {
+        self.display.create_char(location, charmap)
+    }
+
+    fn set_display(&mut self, display: Display) -> Result<(), Self::Error> {
+        self.display.set_display(display)
+    }
+
+    fn set_cursor(&mut self, cursor: Cursor) -> Result<(), Self::Error> {
+        self.display.set_cursor(cursor)
+    }
+
+    fn set_blink(&mut self, blink: Blink) -> Result<(), Self::Error> {
+        self.display.set_blink(blink)
+    }
+
+    fn set_backlight(&mut self, backlight: Backlight) -> Result<(), Self::Error> {
+        self.display.set_backlight(backlight)
+    }
+}
@@ -0,0 +1,48 @@
+//! Errors reported by the driver.
+
+use core::fmt;
+
+/// Error of a driver operation, either from the bus or from the driver itself
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error<E> {
+    /// The bus reported an error
+    Bus(E),
+    /// More custom characters are needed than there are CGRAM slots available
+    CgramFull,
+    /// The CGRAM location is above 7
+    InvalidCgramSlot,
+    /// More lines were given than the display has rows
+    TooManyLines,
+    /// The column or row is outside the display
+    InvalidPosition,
+    /// The busy flag stayed set, e.g. because the R/W pin is not wired
+    Busy,
+    /// No backpack answered on any of the probed addresses
+    NoDevice,
+    /// Work started with `Lcd::start_print` is not done yet, call `Lcd::poll`
+    /// again
+    WouldBlock,
+    /// The shared display was not stored yet, or is in use by an enclosing call
+    Unavailable,
+    /// The bus cannot do what was asked, e.g. read from the display
+    Unsupported,
+}
+
+impl<E: fmt::Debug> fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Bus(error) => write!(f, "bus error: {error:?}"),
+            Error::CgramFull => f.write_str("not enough CGRAM slots available"),
+            Error::InvalidCgramSlot => f.write_str("CGRAM location above 7"),
+            Error::TooManyLines => f.write_str("more lines than the display has rows"),
+            Error::InvalidPosition => f.write_str("position outside the display"),
+            Error::Busy => f.write_str("the display stayed busy"),
+            Error::NoDevice => f.write_str("no backpack found on the I2C bus"),
+            Error::WouldBlock => f.write_str("the display is still being updated"),
+            Error::Unavailable => f.write_str("the shared display is not available"),
+            Error::Unsupported => f.write_str("not supported by the bus"),
+        }
+    }
+}
+
+impl<E: fmt::Debug> core::error::Error for Error<E> {}
@@ -0,0 +1,907 @@
+//! Display shared through a `static`, for interrupt and fault handlers.
+
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+
+#[cfg(feature = "latency-histogram")]
+use crate::LatencyHistogram;
+use crate::{
+    Backlight, Blink, Cursor, Display, DisplayControl, DropPolicy, Entries, Error, FrameStats,
+    Heartbeat, Lcd, LcdBus, Pattern, Progress, RomVariant, Settings, Shift, WaitStrategy,
+};
+
+/// A display that can be reached from anywhere, including interrupt and fault
+/// handlers, without passing it down every call chain.
+///
+/// Declare it as a `static`, hand it the display once with `init`, then use it
+/// with `with`, which runs inside a critical section:
+///
+/// ```ignore
+/// static LCD: Global<Lcd<Pcf8574<I2c0>, Delay>> = Global::new();
+///
+/// LCD.init(lcd);
+///
+/// #[exception]
+/// unsafe fn HardFault(_frame: &ExceptionFrame) -> ! {
+///     LCD.with(|lcd| {
+///         let _ = lcd.clear();
+///         let _ = lcd.print("HARD FAULT");
+///     });
+///     loop {}
+/// }
+/// ```
+pub struct Global<T> {
+    lcd: Mutex<RefCell<Option<T>>>,
+}
+
+impl<T> Global<T> {
+    pub const fn new() -> Self {
+        Global {
+            lcd: Mutex::new(RefCell::new(None)),
+        }
+    }
+
+    /// Store the display, returning the one stored before, if any
+    pub fn init(&self, lcd: T) -> Option<T> {
+        critical_section::with(|cs| self.lcd.borrow(cs).replace(Some(lcd)))
+    }
+
+    /// Take the display back
+    pub fn take(&self) -> Option<T> {
+        critical_section::with(|cs| self.lcd.borrow(cs).take())
+    }
+
+    /// Run `f` on the display inside a critical section. Returns `None` if no
+    /// display was stored, or if it is already in use by an enclosing `with`.
+    pub fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+        critical_section::with(|cs| {
+            let mut lcd = self.lcd.borrow(cs).try_borrow_mut().ok()?;
+            lcd.as_mut().map(f)
+        })
+    }
+}
+
+impl<T> Default for Global<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A display shared between interrupt handlers and the main loop, whose
+/// methods each take the lock themselves, inside a critical section.
+///
+/// ```ignore
+/// static LCD: SharedLcd<Pcf8574<I2c0>, Delay> = SharedLcd::new();
+///
+/// LCD.init(lcd);
+///
+/// #[interrupt]
+/// fn TIMER0() {
+///     let _ = LCD.print_at(0, 1, "tick");
+/// }
+/// ```
+///
+/// Each call blocks interrupts for as long as it talks to the display. Use
+/// `with` to run several calls under one lock.
+///
+/// Methods that report errors return `Error::Unavailable` if the display is
+/// not stored or already in use, the others return `None`. `row_writer`,
+/// `row_str`, `save_settings` and `load_settings`, that borrow the display or
+/// report errors of their own, are reached through `with`.
+pub struct SharedLcd<B, D> {
+    lcd: Global<Lcd<B, D>>,
+}
+
+impl<B, D> SharedLcd<B, D> {
+    pub const fn new() -> Self {
+        SharedLcd { lcd: Global::new() }
+    }
+
+    /// Store the display, returning the one stored before, if any
+    pub fn init(&self, lcd: Lcd<B, D>) -> Option<Lcd<B, D>> {
+        self.lcd.init(lcd)
+    }
+
+    /// Take the display back
+    pub fn take(&self) -> Option<Lcd<B, D>> {
+        self.lcd.take()
+    }
+
+    /// Run `f` on the display inside a critical section. Returns `None` if no
+    /// display was stored, or if it is already in use by an enclosing call.
+    pub fn with<R>(&self, f: impl FnOnce(&mut Lcd<B, D>) -> R) -> Option<R> {
+        self.lcd.with(f)
+    }
+}
+
+impl<B: LcdBus, D: WaitStrategy> SharedLcd<B, D> {
+    // Run `f` on the display, or report it could not be reached
+    fn locked<R>(
+        &self,
+        f: impl FnOnce(&mut Lcd<B, D>) -> Result<R, Error<B::Error>>,
+    ) -> Result<R, Error<B::Error>> {
+        self.lcd.with(f).unwrap_or(Err(Error::Unavailable))
+    }
+
+    /**
+    See `Lcd::clear`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn clear(&self) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.clear())
+    }
+
+    /**
+    See `Lcd::home`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn home(&self) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.home())
+    }
+
+    /**
+    See `Lcd::set_cursor_position`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn set_cursor_position(&self, col: u8, row: u8) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.set_cursor_position(col, row))
+    }
+
+    /**
+    See `Lcd::try_set_cursor_position`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn try_set_cursor_position(&self, col: u8, row: u8) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.try_set_cursor_position(col, row))
+    }
+
+    /**
+    See `Lcd::set_char_at`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn set_char_at(&self, col: u8, row: u8, c: char) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.set_char_at(col, row, c))
+    }
+
+    /// See `Lcd::set_rom_variant`. Returns `None` if the display is not stored or already in use.
+    pub fn set_rom_variant(&self, rom: RomVariant) -> Option<()> {
+        self.lcd.with(|lcd| lcd.set_rom_variant(rom))
+    }
+
+    /// See `Lcd::rom_variant`. Returns `None` if the display is not stored or already in use.
+    pub fn rom_variant(&self) -> Option<RomVariant> {
+        self.lcd.with(|lcd| lcd.rom_variant())
+    }
+
+    /**
+    See `Lcd::detect_rom_variant`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn detect_rom_variant<F: FnOnce() -> bool>(
+        &self,
+        shows_yen: F,
+    ) -> Result<RomVariant, Error<B::Error>> {
+        self.locked(|lcd| lcd.detect_rom_variant(shows_yen))
+    }
+
+    /// See `Lcd::set_replacement_char`. Returns `None` if the display is not stored or already in use.
+    pub fn set_replacement_char(&self, code: u8) -> Option<()> {
+        self.lcd.with(|lcd| lcd.set_replacement_char(code))
+    }
+
+    /// See `Lcd::set_ellipsis`. Returns `None` if the display is not stored or already in use.
+    pub fn set_ellipsis(&self, c: char) -> Option<()> {
+        self.lcd.with(|lcd| lcd.set_ellipsis(c))
+    }
+
+    /**
+    See `Lcd::load_glyphs`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn load_glyphs(&self, glyphs: &[(char, [u8; 8])]) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.load_glyphs(glyphs))
+    }
+
+    /**
+    See `Lcd::create_char`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn create_char(&self, location: u8, charmap: [u8; 8]) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.create_char(location, charmap))
+    }
+
+    /**
+    See `Lcd::create_tall_char`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn create_tall_char(&self, location: u8, charmap: [u8; 10]) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.create_tall_char(location, charmap))
+    }
+
+    /**
+    See `Lcd::set_display`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn set_display(&self, display: Display) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.set_display(display))
+    }
+
+    /**
+    See `Lcd::set_cursor`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn set_cursor(&self, cursor: Cursor) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.set_cursor(cursor))
+    }
+
+    /**
+    See `Lcd::set_blink`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn set_blink(&self, blink: Blink) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.set_blink(blink))
+    }
+
+    /**
+    See `Lcd::set_entry_mode`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn set_entry_mode(&self, entries: Entries, shift: Shift) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.set_entry_mode(entries, shift))
+    }
+
+    /**
+    See `Lcd::set_autoscroll`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn set_autoscroll(&self, autoscroll: bool) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.set_autoscroll(autoscroll))
+    }
+
+    /// See `Lcd::set_line_wrap`. Returns `None` if the display is not stored or already in use.
+    pub fn set_line_wrap(&self, wrap: bool) -> Option<()> {
+        self.lcd.with(|lcd| lcd.set_line_wrap(wrap))
+    }
+
+    /**
+    See `Lcd::set_text_direction`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn set_text_direction(&self, entries: Entries) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.set_text_direction(entries))
+    }
+
+    /**
+    See `Lcd::left_to_right`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn left_to_right(&self) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.left_to_right())
+    }
+
+    /**
+    See `Lcd::right_to_left`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn right_to_left(&self) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.right_to_left())
+    }
+
+    /**
+    See `Lcd::scroll_display_left`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn scroll_display_left(&self) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.scroll_display_left())
+    }
+
+    /**
+    See `Lcd::scroll_display_right`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn scroll_display_right(&self) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.scroll_display_right())
+    }
+
+    /**
+    See `Lcd::move_cursor_left`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn move_cursor_left(&self) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.move_cursor_left())
+    }
+
+    /**
+    See `Lcd::move_cursor_right`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn move_cursor_right(&self) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.move_cursor_right())
+    }
+
+    /**
+    See `Lcd::backspace`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn backspace(&self) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.backspace())
+    }
+
+    /**
+    See `Lcd::set_backlight`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn set_backlight(&self, backlight: Backlight) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.set_backlight(backlight))
+    }
+
+    /**
+    See `Lcd::reinitialize`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn reinitialize(&self) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.reinitialize())
+    }
+
+    /// See `Lcd::health_check`. Returns `None` if the display is not stored or already in use.
+    pub fn health_check(&self) -> Option<bool> {
+        self.lcd.with(|lcd| lcd.health_check())
+    }
+
+    /**
+    See `Lcd::sleep`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn sleep(&self) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.sleep())
+    }
+
+    /**
+    See `Lcd::wake`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn wake(&self) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.wake())
+    }
+
+    /**
+    See `Lcd::read_address_counter`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn read_address_counter(&self) -> Result<u8, Error<B::Error>> {
+        self.locked(|lcd| lcd.read_address_counter())
+    }
+
+    /**
+    See `Lcd::read_char_at`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn read_char_at(&self, col: u8, row: u8) -> Result<char, Error<B::Error>> {
+        self.locked(|lcd| lcd.read_char_at(col, row))
+    }
+
+    /**
+    See `Lcd::read_row`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn read_row(&self, row: u8, buf: &mut [u8]) -> Result<usize, Error<B::Error>> {
+        self.locked(|lcd| lcd.read_row(row, buf))
+    }
+
+    /**
+    See `Lcd::read_char`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn read_char(&self, location: u8) -> Result<[u8; 8], Error<B::Error>> {
+        self.locked(|lcd| lcd.read_char(location))
+    }
+
+    /// See `Lcd::is_asleep`. Returns `None` if the display is not stored or already in use.
+    pub fn is_asleep(&self) -> Option<bool> {
+        self.lcd.with(|lcd| lcd.is_asleep())
+    }
+
+    /**
+    See `Lcd::blink_backlight`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn blink_backlight(&self, count: u32, period_ms: u32) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.blink_backlight(count, period_ms))
+    }
+
+    /// See `Lcd::set_drop_policy`. Returns `None` if the display is not stored or already in use.
+    pub fn set_drop_policy(&self, policy: DropPolicy) -> Option<()> {
+        self.lcd.with(|lcd| lcd.set_drop_policy(policy))
+    }
+
+    /// See `Lcd::cols`. Returns `None` if the display is not stored or already in use.
+    pub fn cols(&self) -> Option<u8> {
+        self.lcd.with(|lcd| lcd.cols())
+    }
+
+    /// See `Lcd::rows`. Returns `None` if the display is not stored or already in use.
+    pub fn rows(&self) -> Option<u8> {
+        self.lcd.with(|lcd| lcd.rows())
+    }
+
+    /// See `Lcd::geometry`. Returns `None` if the display is not stored or already in use.
+    pub fn geometry(&self) -> Option<(u8, u8)> {
+        self.lcd.with(|lcd| lcd.geometry())
+    }
+
+    /// See `Lcd::cursor_position`. Returns `None` if the display is not stored or already in use.
+    pub fn cursor_position(&self) -> Option<(u8, u8)> {
+        self.lcd.with(|lcd| lcd.cursor_position())
+    }
+
+    /// See `Lcd::backlight`. Returns `None` if the display is not stored or already in use.
+    pub fn backlight(&self) -> Option<Backlight> {
+        self.lcd.with(|lcd| lcd.backlight())
+    }
+
+    /// See `Lcd::display`. Returns `None` if the display is not stored or already in use.
+    pub fn display(&self) -> Option<Display> {
+        self.lcd.with(|lcd| lcd.display())
+    }
+
+    /// See `Lcd::cursor`. Returns `None` if the display is not stored or already in use.
+    pub fn cursor(&self) -> Option<Cursor> {
+        self.lcd.with(|lcd| lcd.cursor())
+    }
+
+    /// See `Lcd::blink`. Returns `None` if the display is not stored or already in use.
+    pub fn blink(&self) -> Option<Blink> {
+        self.lcd.with(|lcd| lcd.blink())
+    }
+
+    /// See `Lcd::control`. Returns `None` if the display is not stored or already in use.
+    pub fn control(&self) -> Option<DisplayControl> {
+        self.lcd.with(|lcd| lcd.control())
+    }
+
+    /// See `Lcd::settings`. Returns `None` if the display is not stored or already in use.
+    pub fn settings(&self) -> Option<Settings> {
+        self.lcd.with(|lcd| lcd.settings())
+    }
+
+    /**
+    See `Lcd::apply_settings`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn apply_settings(&self, settings: &Settings) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.apply_settings(settings))
+    }
+
+    /**
+    See `Lcd::set_buffering`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn set_buffering(&self, buffering: bool) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.set_buffering(buffering))
+    }
+
+    /**
+    See `Lcd::flush`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn flush(&self) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.flush())
+    }
+
+    /**
+    See `Lcd::flush_chunk`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn flush_chunk(&self, max_bytes: u32) -> Result<Progress, Error<B::Error>> {
+        self.locked(|lcd| lcd.flush_chunk(max_bytes))
+    }
+
+    /**
+    See `Lcd::start_print`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn start_print(&self, s: &str) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.start_print(s))
+    }
+
+    /// See `Lcd::start_clear`. Returns `None` if the display is not stored or already in use.
+    pub fn start_clear(&self) -> Option<()> {
+        self.lcd.with(|lcd| lcd.start_clear())
+    }
+
+    /**
+    See `Lcd::poll`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn poll(&self, now_us: u32) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.poll(now_us))
+    }
+
+    /// See `Lcd::set_heartbeat`. Returns `None` if the display is not stored or already in use.
+    pub fn set_heartbeat(&self, heartbeat: Option<Heartbeat>) -> Option<()> {
+        self.lcd.with(|lcd| lcd.set_heartbeat(heartbeat))
+    }
+
+    /**
+    See `Lcd::tick`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn tick(&self) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.tick())
+    }
+
+    /// See `Lcd::set_clock`. Returns `None` if the display is not stored or already in use.
+    pub fn set_clock(&self, clock: fn() -> u32) -> Option<()> {
+        self.lcd.with(|lcd| lcd.set_clock(clock))
+    }
+
+    /// See `Lcd::frame_stats`. Returns `None` if the display is not stored or already in use.
+    pub fn frame_stats(&self) -> Option<FrameStats> {
+        self.lcd.with(|lcd| *lcd.frame_stats())
+    }
+
+    /// See `Lcd::reset_frame_stats`. Returns `None` if the display is not stored or already in use.
+    pub fn reset_frame_stats(&self) -> Option<()> {
+        self.lcd.with(|lcd| lcd.reset_frame_stats())
+    }
+
+    /// See `Lcd::latency_histogram`. Returns `None` if the display is not stored or already in use.
+    #[cfg(feature = "latency-histogram")]
+    pub fn latency_histogram(&self) -> Option<LatencyHistogram> {
+        self.lcd.with(|lcd| *lcd.latency_histogram())
+    }
+
+    /// See `Lcd::reset_latency_histogram`. Returns `None` if the display is not stored or already in use.
+    #[cfg(feature = "latency-histogram")]
+    pub fn reset_latency_histogram(&self) -> Option<()> {
+        self.lcd.with(|lcd| lcd.reset_latency_histogram())
+    }
+
+    /// See `Lcd::char_at`. Returns `None` if the display is not stored or already in use.
+    pub fn char_at(&self, col: u8, row: u8) -> Option<char> {
+        self.lcd.with(|lcd| lcd.char_at(col, row)).flatten()
+    }
+
+    /// See `Lcd::capture`. Returns `None` if the display is not stored or already in use.
+    pub fn capture(&self, buf: &mut [u8]) -> Option<usize> {
+        self.lcd.with(|lcd| lcd.capture(buf))
+    }
+
+    /// See `Lcd::content_hash`. Returns `None` if the display is not stored or already in use.
+    pub fn content_hash(&self) -> Option<u32> {
+        self.lcd.with(|lcd| lcd.content_hash())
+    }
+
+    /**
+    See `Lcd::print`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn print(&self, s: &str) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.print(s))
+    }
+
+    /**
+    See `Lcd::scroll_text`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn scroll_text(
+        &self,
+        row: u8,
+        text: &str,
+        step_ms: u32,
+        passes: u32,
+    ) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.scroll_text(row, text, step_ms, passes))
+    }
+
+    /**
+    See `Lcd::show_test_pattern`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn show_test_pattern(&self, pattern: Pattern) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.show_test_pattern(pattern))
+    }
+
+    /**
+    See `Lcd::print_at`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn print_at(&self, col: u8, row: u8, s: &str) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.print_at(col, row, s))
+    }
+
+    /**
+    See `Lcd::print_truncated`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn print_truncated(&self, row: u8, s: &str) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.print_truncated(row, s))
+    }
+
+    /**
+    See `Lcd::print_right_aligned`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn print_right_aligned(&self, row: u8, s: &str) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.print_right_aligned(row, s))
+    }
+
+    /**
+    See `Lcd::clear_row`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn clear_row(&self, row: u8) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.clear_row(row))
+    }
+
+    /**
+    See `Lcd::clear_region`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn clear_region(&self, col: u8, row: u8, len: u8) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.clear_region(col, row, len))
+    }
+
+    /**
+    See `Lcd::print_screen`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn print_screen(&self, lines: &[&str]) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.print_screen(lines))
+    }
+
+    /**
+    See `Lcd::overwrite_line`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn overwrite_line(&self, row: u8, s: &str) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.overwrite_line(row, s))
+    }
+
+    /**
+    See `Lcd::print_centered`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn print_centered(&self, row: u8, s: &str) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.print_centered(row, s))
+    }
+
+    /**
+    See `Lcd::write`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn write(&self, value: u8) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.write(value))
+    }
+
+    /**
+    See `Lcd::write_bytes`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn write_bytes(&self, bytes: &[u8]) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.write_bytes(bytes))
+    }
+
+    /**
+    See `Lcd::write_iter`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::Unavailable` if the display is not stored or already in use.
+    */
+    pub fn write_iter<I: IntoIterator<Item = u8>>(&self, bytes: I) -> Result<(), Error<B::Error>> {
+        self.locked(|lcd| lcd.write_iter(bytes))
+    }
+}
+
+impl<B, D> Default for SharedLcd<B, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,213 @@
+//! Transport through MCU pins wired straight to the display.
+
+use embedded_hal::digital::OutputPin;
+
+use crate::{Backlight, BitMode, LcdBus, Register, WaitStrategy};
+
+/// Time a command or character takes to execute, at most 37µs, while the pins
+/// cannot read the busy flag
+const EXECUTION_US: u32 = 50;
+
+/// Display driven in 4-bit mode through its RS, E and D4–D7 pins, with R/W
+/// tied low, and optionally a pin switching the backlight, active high.
+///
+/// The pins share one error type, like most HALs do. The busy flag cannot be
+/// read, so use a delay rather than `BusyFlag` as the wait strategy.
+pub struct Gpio4Bit<RS, EN, D4, D5, D6, D7, BL> {
+    rs: RS,
+    en: EN,
+    d4: D4,
+    d5: D5,
+    d6: D6,
+    d7: D7,
+    backlight: Option<BL>,
+}
+
+impl<RS, EN, D4, D5, D6, D7, BL> Gpio4Bit<RS, EN, D4, D5, D6, D7, BL>
+where
+    RS: OutputPin,
+    EN: OutputPin<Error = RS::Error>,
+    D4: OutputPin<Error = RS::Error>,
+    D5: OutputPin<Error = RS::Error>,
+    D6: OutputPin<Error = RS::Error>,
+    D7: OutputPin<Error = RS::Error>,
+    BL: OutputPin<Error = RS::Error>,
+{
+    pub fn new(rs: RS, en: EN, d4: D4, d5: D5, d6: D6, d7: D7, backlight: Option<BL>) -> Self {
+        Gpio4Bit {
+            rs,
+            en,
+            d4,
+            d5,
+            d6,
+            d7,
+            backlight,
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub fn release(self) -> (RS, EN, D4, D5, D6, D7, Option<BL>) {
+        (
+            self.rs,
+            self.en,
+            self.d4,
+            self.d5,
+            self.d6,
+            self.d7,
+            self.backlight,
+        )
+    }
+
+    // Put the high nibble of `value` on D4–D7 and clock it in
+    fn write4bits(
+        &mut self,
+        value: u8,
+        register: Register,
+        wait: &mut impl WaitStrategy,
+    ) -> Result<(), RS::Error> {
+        set(&mut self.rs, register == Register::Data)?;
+        set(&mut self.d4, value & 0x10 != 0)?;
+        set(&mut self.d5, value & 0x20 != 0)?;
+        set(&mut self.d6, value & 0x40 != 0)?;
+        set(&mut self.d7, value & 0x80 != 0)?;
+
+        self.en.set_high()?;
+        wait.wait_us(1);
+        self.en.set_low()?;
+        wait.wait_us(1);
+
+        Ok(())
+    }
+}
+
+fn set<P: OutputPin>(pin: &mut P, high: bool) -> Result<(), P::Error> {
+    if high { pin.set_high() } else { pin.set_low() }
+}
+
+impl<RS, EN, D4, D5, D6, D7, BL> LcdBus for Gpio4Bit<RS, EN, D4, D5, D6, D7, BL>
+where
+    RS: OutputPin,
+    EN: OutputPin<Error = RS::Error>,
+    D4: OutputPin<Error = RS::Error>,
+    D5: OutputPin<Error = RS::Error>,
+    D6: OutputPin<Error = RS::Error>,
+    D7: OutputPin<Error = RS::Error>,
+    BL: OutputPin<Error = RS::Error>,
+{
+    type Error = RS::Error;
+
+    fn write_nibble(
+        &mut self,
+        value: u8,
+        register: Register,
+        wait: &mut impl WaitStrategy,
+    ) -> Result<(), Self::Error> {
+        self.write4bits(value, register, wait)?;
+        wait.wait_us(EXECUTION_US);
+        Ok(())
+    }
+
+    fn write_byte(
+        &mut self,
+        value: u8,
+        register: Register,
+        wait: &mut impl WaitStrategy,
+    ) -> Result<(), Self::Error> {
+        self.write4bits(value, register, wait)?;
+        self.write4bits(value << 4, register, wait)?;
+        wait.wait_us(EXECUTION_US);
+        Ok(())
+    }
+
+    fn set_backlight(&mut self, backlight: Backlight) -> Result<(), Self::Error> {
+        match &mut self.backlight {
+            Some(pin) => set(pin, backlight == Backlight::On),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Display driven in 8-bit mode through its RS, E and D0–D7 pins, with R/W
+/// tied low, and optionally a pin switching the backlight, active high. Each
+/// byte takes a single enable pulse, half the pin toggling of `Gpio4Bit`.
+///
+/// The data pins are of one type, such as the degraded pins most HALs offer,
+/// and share their error type with the other pins. The busy flag cannot be
+/// read, so use a delay rather than `BusyFlag` as the wait strategy.
+pub struct Gpio8Bit<RS, EN, D, BL> {
+    rs: RS,
+    en: EN,
+    data: [D; 8],
+    backlight: Option<BL>,
+}
+
+impl<RS, EN, D, BL> Gpio8Bit<RS, EN, D, BL>
+where
+    RS: OutputPin,
+    EN: OutputPin<Error = RS::Error>,
+    D: OutputPin<Error = RS::Error>,
+    BL: OutputPin<Error = RS::Error>,
+{
+    /// `data` holds D0 to D7, in order
+    pub fn new(rs: RS, en: EN, data: [D; 8], backlight: Option<BL>) -> Self {
+        Gpio8Bit {
+            rs,
+            en,
+            data,
+            backlight,
+        }
+    }
+
+    pub fn release(self) -> (RS, EN, [D; 8], Option<BL>) {
+        (self.rs, self.en, self.data, self.backlight)
+    }
+}
+
+impl<RS, EN, D, BL> LcdBus for Gpio8Bit<RS, EN, D, BL>
+where
+    RS: OutputPin,
+    EN: OutputPin<Error = RS::Error>,
+    D: OutputPin<Error = RS::Error>,
+    BL: OutputPin<Error = RS::Error>,
+{
+    type Error = RS::Error;
+
+    fn write_nibble(
+        &mut self,
+        value: u8,
+        register: Register,
+        wait: &mut impl WaitStrategy,
+    ) -> Result<(), Self::Error> {
+        self.write_byte(value & 0xf0, register, wait)
+    }
+
+    fn write_byte(
+        &mut self,
+        value: u8,
+        register: Register,
+        wait: &mut impl WaitStrategy,
+    ) -> Result<(), Self::Error> {
+        set(&mut self.rs, register == Register::Data)?;
+        for (bit, pin) in self.data.iter_mut().enumerate() {
+            set(pin, value & (1 << bit) != 0)?;
+        }
+
+        self.en.set_high()?;
+        wait.wait_us(1);
+        self.en.set_low()?;
+        wait.wait_us(EXECUTION_US);
+
+        Ok(())
+    }
+
+    fn set_backlight(&mut self, backlight: Backlight) -> Result<(), Self::Error> {
+        match &mut self.backlight {
+            Some(pin) => set(pin, backlight == Backlight::On),
+            None => Ok(()),
+        }
+    }
+
+    fn bit_mode(&self) -> BitMode {
+        BitMode::Bit8
+    }
+}
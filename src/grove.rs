@@ -0,0 +1,107 @@
+//! Seeed Grove RGB LCDs.
+
+use embedded_hal::i2c::I2c;
+
+use crate::{
+    Address, Backlight, BitMode, LcdBus, NativeI2c, Pca963x, Register, RgbBacklight, WaitStrategy,
+};
+
+/// The Grove 16x2 RGB LCD: an AIP31068 controller taking HD44780 commands
+/// straight over I2C, in 8-bit mode, and a PCA9633 driving the RGB backlight,
+/// both on the same bus.
+///
+/// `set_backlight` turns the backlight on with the color last given to
+/// `set_rgb`, white at first, or off.
+pub struct GroveRgb<I2C> {
+    lcd: NativeI2c<I2C>,
+    backlight: Backlight,
+    /// Color shown while the backlight is on
+    color: [u8; 3],
+    /// Whether the PCA9633 was woken up
+    configured: bool,
+}
+
+impl<I2C: I2c> GroveRgb<I2C> {
+    /// Address of the AIP31068
+    pub const LCD_ADDRESS: u8 = 0x3E;
+
+    pub fn new(i2c: I2C) -> Self {
+        // The Co bit is set, as the controller only takes one command at a time
+        let lcd =
+            NativeI2c::with_control_bytes(i2c, Address::unchecked(Self::LCD_ADDRESS), 0x80, 0x40);
+        GroveRgb {
+            lcd,
+            backlight: Backlight::On,
+            color: [0xff; 3],
+            configured: false,
+        }
+    }
+
+    /// Give back the I2C bus
+    pub fn release(self) -> I2C {
+        self.lcd.release()
+    }
+
+    // Show the color, or black while the backlight is off
+    fn write_color(&mut self) -> Result<(), I2C::Error> {
+        let mut rgb = Pca963x::new(&mut self.lcd.i2c, Pca963x::<I2C>::GROVE_ADDRESS);
+        if !self.configured {
+            rgb.init()?;
+            self.configured = true;
+        }
+
+        let [red, green, blue] = match self.backlight {
+            Backlight::On => self.color,
+            Backlight::Off => [0; 3],
+        };
+        rgb.set_rgb(red, green, blue)
+    }
+}
+
+impl<I2C: I2c> RgbBacklight for GroveRgb<I2C> {
+    type Error = I2C::Error;
+
+    fn set_rgb(&mut self, red: u8, green: u8, blue: u8) -> Result<(), Self::Error> {
+        self.color = [red, green, blue];
+        self.write_color()
+    }
+}
+
+impl<I2C: I2c> LcdBus for GroveRgb<I2C> {
+    type Error = I2C::Error;
+
+    fn write_nibble(
+        &mut self,
+        value: u8,
+        register: Register,
+        wait: &mut impl WaitStrategy,
+    ) -> Result<(), Self::Error> {
+        self.lcd.write_nibble(value, register, wait)
+    }
+
+    fn write_byte(
+        &mut self,
+        value: u8,
+        register: Register,
+        wait: &mut impl WaitStrategy,
+    ) -> Result<(), Self::Error> {
+        self.lcd.write_byte(value, register, wait)
+    }
+
+    fn set_backlight(&mut self, backlight: Backlight) -> Result<(), Self::Error> {
+        self.backlight = backlight;
+        self.write_color()
+    }
+
+    fn bit_mode(&self) -> BitMode {
+        BitMode::Bit8
+    }
+
+    fn command_bytes(&self) -> u32 {
+        self.lcd.command_bytes()
+    }
+
+    fn bytes_written(&self) -> u32 {
+        self.lcd.bytes_written()
+    }
+}
@@ -0,0 +1,60 @@
+//! Owning the display from one task.
+
+use core::ops::{Deref, DerefMut};
+
+use crate::Lcd;
+
+/// A display owned by one task or thread, to be moved into it.
+///
+/// `Lcd` is `Send` whenever its bus and delay are, as most HAL peripherals
+/// are, and is not meant to be used from several tasks at once: its methods
+/// take `&mut self`. Move it into the task that drives the display, here or
+/// as a plain `Lcd`, and send that task what to show, e.g. through a channel
+/// of the RTOS. For interrupt handlers, see `SharedLcd`, with the `global`
+/// feature.
+///
+/// ```ignore
+/// let handle = LcdHandle::new(lcd);
+/// std::thread::spawn(move || {
+///     let mut lcd = handle;
+///     for message in receiver {
+///         lcd.print_at(0, 0, &message).unwrap();
+///     }
+/// });
+/// ```
+pub struct LcdHandle<B, D> {
+    lcd: Lcd<B, D>,
+}
+
+impl<B: Send, D: Send> LcdHandle<B, D> {
+    pub fn new(lcd: Lcd<B, D>) -> Self {
+        LcdHandle { lcd }
+    }
+}
+
+impl<B, D> LcdHandle<B, D> {
+    /// Take the display back
+    pub fn into_inner(self) -> Lcd<B, D> {
+        self.lcd
+    }
+}
+
+impl<B, D> Deref for LcdHandle<B, D> {
+    type Target = Lcd<B, D>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.lcd
+    }
+}
+
+impl<B, D> DerefMut for LcdHandle<B, D> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.lcd
+    }
+}
+
+// Fails to build if a field ever keeps `Lcd` from being sent to another task
+#[allow(dead_code)]
+fn assert_send<B: Send, D: Send>(handle: LcdHandle<B, D>) -> impl Send {
+    handle
+}
@@ -0,0 +1,106 @@
+//! Transport through a 74HC595 shift register on an SPI bus.
+
+use embedded_hal::spi::SpiDevice;
+
+use crate::{Backlight, LcdBus, Register, WaitStrategy};
+
+const RS: u8 = 0x02;
+const ENABLE: u8 = 0x04;
+const BACKLIGHT: u8 = 0x80;
+
+/// A 74HC595 shift register latching every byte written on an SPI bus, driving
+/// the display in 4-bit mode with RS on Q1, E on Q2, D4–D7 on Q3–Q6 and the
+/// backlight on Q7, as on the Adafruit I2C/SPI backpack. R/W is tied low, so
+/// the busy flag cannot be read.
+///
+/// The latch clock (RCLK) goes on the chip select of the `SpiDevice`, whose
+/// rising edge at the end of each write updates the outputs.
+pub struct Hc595<SPI> {
+    spi: SPI,
+    backlight: Backlight,
+    /// Bytes written, wrapping
+    bytes: u32,
+}
+
+impl<SPI: SpiDevice> Hc595<SPI> {
+    pub fn new(spi: SPI) -> Self {
+        Hc595 {
+            spi,
+            backlight: Backlight::On,
+            bytes: 0,
+        }
+    }
+
+    /// Give back the SPI device
+    pub fn release(self) -> SPI {
+        self.spi
+    }
+
+    // Put the high nibble of `value` on D4–D7 and clock it in
+    fn write4bits(
+        &mut self,
+        value: u8,
+        register: Register,
+        wait: &mut impl WaitStrategy,
+    ) -> Result<(), SPI::Error> {
+        let mut pins = (value & 0xf0) >> 1;
+        if register == Register::Data {
+            pins |= RS;
+        }
+
+        self.latch(pins)?;
+        self.latch(pins | ENABLE)?; // En high
+        wait.wait_us(1);
+        self.latch(pins)?; // En low
+        wait.wait_us(1);
+
+        Ok(())
+    }
+
+    fn latch(&mut self, pins: u8) -> Result<(), SPI::Error> {
+        let backlight = match self.backlight {
+            Backlight::On => BACKLIGHT,
+            Backlight::Off => 0,
+        };
+        self.spi.write(&[pins | backlight])?;
+        self.bytes = self.bytes.wrapping_add(1);
+        Ok(())
+    }
+}
+
+impl<SPI: SpiDevice> LcdBus for Hc595<SPI> {
+    type Error = SPI::Error;
+
+    fn write_nibble(
+        &mut self,
+        value: u8,
+        register: Register,
+        wait: &mut impl WaitStrategy,
+    ) -> Result<(), Self::Error> {
+        self.write4bits(value, register, wait)
+    }
+
+    fn write_byte(
+        &mut self,
+        value: u8,
+        register: Register,
+        wait: &mut impl WaitStrategy,
+    ) -> Result<(), Self::Error> {
+        self.write4bits(value, register, wait)?;
+        self.write4bits(value << 4, register, wait)
+    }
+
+    fn set_backlight(&mut self, backlight: Backlight) -> Result<(), Self::Error> {
+        self.backlight = backlight;
+        self.latch(0)
+    }
+
+    fn command_bytes(&self) -> u32 {
+        // Three latched bytes for each nibble
+        6
+    }
+
+    fn bytes_written(&self) -> u32 {
+        self.bytes
+    }
+}
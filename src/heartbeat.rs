@@ -0,0 +1,22 @@
+//! Cell showing that the firmware is alive.
+
+/// A cell that alternates between two characters on every flush or tick, so
+/// a frozen firmware can be told apart from a screen that just shows static text.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Heartbeat {
+    pub col: u8,
+    pub row: u8,
+    /// ROM codes or CGRAM locations of the two characters to alternate between
+    pub glyphs: [u8; 2],
+}
+
+impl Heartbeat {
+    /// Heartbeat alternating between `*` and a space at the given cell
+    pub const fn new(col: u8, row: u8) -> Self {
+        Heartbeat {
+            col,
+            row,
+            glyphs: *b"* ",
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! Customizable initialization sequence.
+
+use crate::{Display, Error, Lcd, LcdBus, WaitStrategy};
+
+/// Steps of the display initialization, handed to the closure given to
+/// `Lcd::init_with`.
+///
+/// `standard` runs the HD44780 reference flow. Its steps are also available one
+/// by one, so they can be mixed with extra commands, nibbles and delays.
+pub struct InitSequence<'a, B, D> {
+    lcd: &'a mut Lcd<B, D>,
+}
+
+impl<'a, B: LcdBus, D: WaitStrategy> InitSequence<'a, B, D> {
+    pub(crate) fn new(lcd: &'a mut Lcd<B, D>) -> Self {
+        InitSequence { lcd }
+    }
+
+    /// Run every step of the HD44780 reference flow, in order. When turned off
+    /// with `Lcd::clear_on_init`, the display is turned on instead of cleared.
+    pub fn standard(&mut self) -> Result<(), Error<B::Error>> {
+        self.wait_for_power_up()?;
+        self.enter_4bit_mode()?;
+        self.setup()?;
+        self.function_set()?;
+        if self.lcd.clear_on_init {
+            self.clear()?;
+            return self.entry_mode();
+        }
+
+        // Keep the content, which is only shown once the display is on
+        self.lcd.control.display = Display::On;
+        self.display_control()?;
+        self.entry_mode()?;
+        self.lcd.set_ddram_address(0, 0)
+    }
+
+    /// Wait 50ms for the controller to power up, then set the backlight
+    pub fn wait_for_power_up(&mut self) -> Result<(), Error<B::Error>> {
+        self.lcd.wait_for_power_up()
+    }
+
+    /// Send function set three times in 8-bit mode, then switch to 4-bit mode,
+    /// unless the bus is 8-bit wide
+    pub fn enter_4bit_mode(&mut self) -> Result<(), Error<B::Error>> {
+        self.lcd.enter_4bit_mode()
+    }
+
+    /// Run the setup specific to the controller behind the bus, if any, such
+    /// as the power and oscillator settings of OLED and COG controllers
+    pub fn setup(&mut self) -> Result<(), Error<B::Error>> {
+        self.lcd.setup()
+    }
+
+    /// Set the number of lines and the font
+    pub fn function_set(&mut self) -> Result<(), Error<B::Error>> {
+        self.lcd.function_set()
+    }
+
+    /// Clear the display and return the cursor home
+    pub fn clear(&mut self) -> Result<(), Error<B::Error>> {
+        self.lcd.clear()
+    }
+
+    /// Send the display, cursor and blink settings
+    pub fn display_control(&mut self) -> Result<(), Error<B::Error>> {
+        self.lcd.write_display_control()
+    }
+
+    /// Set the text direction and display shift
+    pub fn entry_mode(&mut self) -> Result<(), Error<B::Error>> {
+        self.lcd.write_entry_mode()
+    }
+
+    /// Send a full command byte, as two nibbles
+    pub fn command(&mut self, value: u8) -> Result<(), Error<B::Error>> {
+        self.lcd.command(value)
+    }
+
+    /// Send only the high nibble of `value`, as done while the controller may
+    /// still be in 8-bit mode
+    pub fn write_nibble(&mut self, value: u8) -> Result<(), Error<B::Error>> {
+        self.lcd.write_nibble(value)
+    }
+
+    /// Wait for the given number of milliseconds
+    pub fn delay_ms(&mut self, ms: u32) {
+        self.lcd.delay.wait_ms(ms);
+    }
+
+    /// Wait for the given number of microseconds
+    pub fn delay_us(&mut self, us: u32) {
+        self.lcd.delay.wait_us(us);
+    }
+}
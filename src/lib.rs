@@ -3,10 +3,11 @@
 
 //! Example of use:
 
-//! ```rust
+//! ```ignore
 //! use rppal::{gpio::Gpio, i2c::I2c};
+//! use i2c_lcd_screen::{Address, Backlight, Display, Lcd};
 
-//! static LCD_ADDRESS: u8 = 0x27;
+//! const LCD_ADDRESS: Address = Address::PCF8574;
 
 //! fn setup() {
 
@@ -15,10 +16,10 @@
 //!     let mut i2c = I2c::new().unwrap();
 //!     let mut delay = rppal::hal::Delay;
 
-//!     let mut lcd = screen::Lcd::new(&mut i2c, LCD_ADDRESS, &mut delay).unwrap();
+//!     let mut lcd = Lcd::new(&mut i2c, LCD_ADDRESS, &mut delay, 16, 2).init().unwrap();
 
-//!     lcd.set_display(screen::Display::On).unwrap();
-//!     lcd.set_backlight(screen::Backlight::On).unwrap();
+//!     lcd.set_display(Display::On).unwrap();
+//!     lcd.set_backlight(Backlight::On).unwrap();
 //!     lcd.print("Hello world!").unwrap();
 //! }
 
@@ -26,17 +27,110 @@
 
 #![no_std]
 
+mod address;
+mod big_font;
+mod bitmap;
+mod buffer;
+mod bus;
+mod canvas;
+mod character_display;
+mod charset;
+mod contrast;
+mod error;
+#[cfg(feature = "global")]
+mod global;
+mod gpio;
+mod grove;
+mod handle;
+mod hc595;
+mod heartbeat;
+mod init;
+mod marquee;
+mod mcp23008;
+mod mcp23017;
+mod mirror;
+mod native;
+mod pattern;
+mod pcf8574;
+mod pcf8575;
+mod pin_mapping;
+#[cfg(feature = "embedded-io")]
+mod remote;
+mod rgb;
+mod rom_browser;
+mod row_offsets;
+mod row_writer;
+mod serlcd;
+mod settings;
+mod size;
+mod st7032;
+mod stats;
+#[cfg(feature = "embedded-io")]
+mod terminal;
+mod us2066;
+mod wait;
+
 use core::clone::Clone;
 use core::default::Default;
-use core::fmt::Debug;
-use core::marker::Copy;
+use core::fmt::{self, Debug};
+use core::marker::{Copy, PhantomData};
+use core::mem::ManuallyDrop;
 use core::prelude::rust_2024::derive;
+use core::ptr;
 use core::result::Result::{self, Ok};
-use embedded_hal::{delay::DelayNs, i2c::I2c};
+use embedded_hal::i2c::I2c;
+
+pub use address::{Address, InvalidAddress, scan};
+pub use big_font::BigFont;
+pub use bitmap::Bitmap;
+use buffer::ShadowBuffer;
+pub use bus::{Controllers, LcdBus, Register};
+pub use canvas::Canvas;
+pub use character_display::CharacterDisplay;
+use charset::Charset;
+pub use charset::RomVariant;
+pub use contrast::{Contrast, Mcp4018, Mcp4531, WithContrast, X9c10x};
+pub use error::Error;
+#[cfg(feature = "global")]
+pub use global::{Global, SharedLcd};
+pub use gpio::{Gpio4Bit, Gpio8Bit};
+pub use grove::GroveRgb;
+pub use handle::LcdHandle;
+pub use hc595::Hc595;
+pub use heartbeat::Heartbeat;
+pub use init::InitSequence;
+pub use marquee::Marquee;
+pub use mcp23008::Mcp23008;
+pub use mcp23017::{Buttons, Mcp23017};
+pub use mirror::{Mirror, MirrorError};
+pub use native::NativeI2c;
+pub use pattern::Pattern;
+pub use pcf8574::Pcf8574;
+pub use pcf8575::{Half, Pcf8575};
+pub use pin_mapping::{PinMapping, Polarity};
+#[cfg(feature = "embedded-io")]
+pub use remote::{RemoteMirror, RemoteScreen};
+pub use rgb::{Pca963x, RgbBacklight};
+pub use rom_browser::RomBrowser;
+pub use row_offsets::RowOffsets;
+pub use row_writer::RowWriter;
+pub use serlcd::SerLcd;
+pub use settings::Settings;
+#[cfg(feature = "embedded-storage")]
+pub use settings::SettingsError;
+pub use size::{Lcd16x2, Lcd20x4, Size};
+pub use st7032::St7032i;
+#[cfg(feature = "latency-histogram")]
+pub use stats::LatencyHistogram;
+pub use stats::{FlushStats, FrameStats};
+#[cfg(feature = "embedded-io")]
+pub use terminal::Terminal;
+pub use us2066::Us2066;
+pub use wait::{BusyFlag, Callback, WaitStrategy};
 
 /// Controls the visibility of the non-blinking cursor, which is basically an _ **after** the cursor position.
 /// The cursor position represents where the next character will show up.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Cursor {
     /// Display the non-blinking cursor
     On = 0x02,
@@ -45,7 +139,7 @@ pub enum Cursor {
 }
 
 /// Controls the visibility of the blinking block cursor.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Blink {
     /// Turn the blinking block cursor on
     On = 0x01,
@@ -54,7 +148,7 @@ pub enum Blink {
 }
 
 /// Determines whether the entire LCD is on or off.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Display {
     /// Turn the LCD display on
     On = 0x04,
@@ -63,7 +157,7 @@ pub enum Display {
 }
 
 /// Determines whether the blaclight is on or off.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Backlight {
     /// Turn the backlight on
     On = 0x08,
@@ -137,12 +231,18 @@ pub enum BitAction {
     RegisterSelect = 0x01,
 }
 
+#[derive(Copy, Clone, Debug)]
 pub struct DisplayControl {
     pub cursor: Cursor,
     pub display: Display,
     pub blink: Blink,
     pub backlight: Backlight,
     pub direction: Direction,
+    /// Whether the display shifts on each write, `Shift::INCREMENT` being autoscroll
+    pub shift: Shift,
+    /// Whether the cursor moves right (`Entries::LEFT`, left to right text) or
+    /// left after each write
+    pub entries: Entries,
 }
 
 impl DisplayControl {
@@ -153,6 +253,8 @@ impl DisplayControl {
             blink: Blink::Off,
             backlight: Backlight::On,
             direction: Direction::LEFT,
+            shift: Shift::DECREMENT,
+            entries: Entries::LEFT,
         }
     }
 
@@ -167,66 +269,564 @@ impl Default for DisplayControl {
     }
 }
 
-pub struct Lcd<I2C, D> {
-    i2c: I2C,
+/// Outcome of `Lcd::flush_chunk`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Progress {
+    /// Every pending cell has been sent, `cells` of them by this call
+    Done { cells: u32 },
+    /// `cells` were sent, and some are still pending
+    Pending { cells: u32 },
+}
+
+/// What the driver does to the display when it is dropped
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DropPolicy {
+    /// Leave the display as it is
+    Nothing,
+    /// Turn the backlight off
+    BacklightOff,
+    /// Turn the display off, keeping its content
+    DisplayOff,
+    /// Clear the display
+    Clear,
+    /// Clear the display and turn the backlight off
+    Blank,
+}
+
+/// State of a driver whose display has not been initialized yet
+pub struct Uninitialized;
+
+/// State of a driver whose display is initialized and ready to print
+pub struct Ready;
+
+pub struct Lcd<B, D, S = Ready> {
+    bus: B,
     control: DisplayControl,
-    address: u8,
+    /// Control state to restore on `wake`, while asleep
+    sleeping: Option<DisplayControl>,
     delay: D,
+    cols: u8,
     rows: u8,
     row_offsets: [u8; 4],
+    /// Tracked (col, row) of the cursor
+    position: (u8, u8),
+    /// Whether the last write, right to left, was on the first column, so the
+    /// next ones are clipped until the cursor moves
+    past_left_edge: bool,
+    /// What is (or, when buffering, will be) on the screen
+    buffer: ShadowBuffer,
+    buffering: bool,
+    /// Whether `print` continues on the next row past the last column
+    wrap: bool,
+    /// Last char shown by `print_truncated` when the text does not fit
+    ellipsis: char,
+    /// Whether the standard initialization clears the display
+    clear_on_init: bool,
+    /// Controllers the next data goes to, on displays made of two
+    selected: Controllers,
+    font: Dots,
+    lines: Lines,
+    drop_policy: DropPolicy,
+    /// Applies `drop_policy`, set once the driver is ready
+    on_drop: Option<fn(&mut Self)>,
+    /// Custom characters uploaded at each CGRAM location
+    glyphs: [Option<[u8; 8]>; 8],
+    heartbeat: Option<Heartbeat>,
+    /// Which of the heartbeat glyphs is shown
+    heartbeat_phase: bool,
+    charset: Charset,
+    /// Contrast level last set, on controllers having a setting for it
+    contrast: Option<u8>,
+    /// Monotonic clock in microseconds, used for timing metrics
+    clock: Option<fn() -> u32>,
+    /// Time at which `poll` may send the next cell
+    ready_at: Option<u32>,
+    frame_stats: FrameStats,
+    #[cfg(feature = "latency-histogram")]
+    latency: LatencyHistogram,
+    state: PhantomData<S>,
+}
+
+impl<I2C: I2c, D: WaitStrategy> Lcd<Pcf8574<I2C>, D, Uninitialized> {
+    /**
+    Create the driver for a display behind a PCF8574 I2C backpack. Nothing is
+    sent to the display until `init` is called.
+
+    `i2c` can be a `&mut` to the bus, or an `embedded-hal-bus` device such as
+    `RefCellDevice` or `CriticalSectionDevice` to share it with other drivers.
+
+    ```
+    # use embedded_hal::{delay::DelayNs, i2c::{ErrorType, I2c, Operation}};
+    # struct Bus;
+    # impl ErrorType for Bus { type Error = core::convert::Infallible; }
+    # impl I2c for Bus {
+    #     fn transaction(&mut self, _: u8, _: &mut [Operation<'_>]) -> Result<(), Self::Error> {
+    #         Ok(())
+    #     }
+    # }
+    # struct Delay;
+    # impl DelayNs for Delay { fn delay_ns(&mut self, _: u32) {} }
+    use core::cell::RefCell;
+    use embedded_hal_bus::i2c::RefCellDevice;
+    use i2c_lcd_screen::{Address, Lcd};
+
+    let mut i2c = Bus;
+    let mut delay = Delay;
+
+    // Borrowed for as long as the display lives
+    let mut lcd = Lcd::new(&mut i2c, Address::PCF8574, &mut delay, 16, 2).init().unwrap();
+    lcd.print("Hello").unwrap();
+    drop(lcd);
+
+    // Shared with another driver
+    let bus = RefCell::new(i2c);
+    let mut lcd = Lcd::new(RefCellDevice::new(&bus), Address::PCF8574, delay, 16, 2)
+        .init()
+        .unwrap();
+    let mut sensor = RefCellDevice::new(&bus);
+    lcd.print("Hello").unwrap();
+    sensor.write(0x76, &[0xf7]).unwrap();
+    ```
+    */
+    pub fn new(i2c: I2C, address: Address, delay: D, cols: u8, rows: u8) -> Self {
+        Self::with_bus(Pcf8574::new(i2c, address), delay, cols, rows)
+    }
+
+    /**
+    Create the driver for a display of `COLS` columns and `ROWS` rows, checked
+    at compile time, see `Size` and its `Lcd16x2` and `Lcd20x4` aliases.
+
+    ```ignore
+    let lcd = Lcd::with_size::<20, 4>(i2c, Address::PCF8574, delay).init()?;
+    ```
+    */
+    pub fn with_size<const COLS: u8, const ROWS: u8>(i2c: I2C, address: Address, delay: D) -> Self {
+        Size::<COLS, ROWS>::new(i2c, address, delay)
+    }
+
+    /**
+    For boards where the backlight bit of the expander is not wired to the
+    backlight, or is used for something else: the bit is left high, as the
+    expander starts up, which on a PCF8574 only means a weak pull-up, and
+    `set_backlight` no longer changes it.
+    */
+    pub fn without_backlight(mut self) -> Self {
+        self.bus.backlight_pin = false;
+        self
+    }
+
+    /**
+    For 40x4 displays, made of two controllers sharing every line but the
+    enable one: `enable` is the expander bit wired to the enable line of the
+    second controller, which shows rows 2 and 3, e.g. `0x08` on backpacks
+    using the backlight bit, which then no longer drives the backlight.
+
+    Both controllers are initialized together, and commands that are not
+    about a position, like `clear` or `set_display`, go to both. The cursor
+    and blink settings then apply to both too, each showing its cursor where
+    it last wrote, so they are best left off. Reading back, including busy
+    flag polling, needs R/W to be wired: the second enable cannot be on its
+    bit.
+    */
+    pub fn second_controller(mut self, enable: u8) -> Self {
+        self.bus.second_enable = enable;
+        self.bus.select(self.selected);
+        self
+    }
+
+    /**
+    For backpacks wiring the display to other expander bits than most, or
+    driving the backlight active low, see `PinMapping`.
+    */
+    pub fn pin_mapping(mut self, mapping: PinMapping) -> Self {
+        self.bus.mapping = mapping;
+        self.bus.select(self.selected);
+        self
+    }
+
+    /**
+    Find the backpack among `Address::ALL`, the default addresses first, and
+    initialize the first one answering. `address` tells which one was used.
+
+    Probing reads a byte from each address, which does not change the outputs
+    of a PCF8574. Other devices on the bus in these ranges could be mistaken
+    for the backpack.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::NoDevice` if no address answered.
+    */
+    pub fn new_autodetect(
+        mut i2c: I2C,
+        delay: D,
+        cols: u8,
+        rows: u8,
+    ) -> Result<Lcd<Pcf8574<I2C>, D>, Error<I2C::Error>> {
+        let address = scan(&mut i2c, &Address::ALL)
+            .next()
+            .ok_or(Error::NoDevice)?;
+
+        Self::new(i2c, address, delay, cols, rows).init()
+    }
+}
+
+impl<I2C: I2c, D: WaitStrategy> Lcd<Pcf8574<I2C>, D> {
+    /// I2C address of the backpack
+    pub fn address(&self) -> Address {
+        self.bus.address()
+    }
+}
+
+impl<I2C: I2c, D: WaitStrategy, S> Lcd<Pcf8574<I2C>, D, S> {
+    /**
+    Give back the I2C bus and the delay, so they can be used for other devices.
+    The display is left as it is, the drop policy is not applied: turn the
+    display off first with `set_display` if needed.
+    */
+    pub fn release(self) -> (I2C, D) {
+        let (bus, delay) = self.into_parts();
+        (bus.release(), delay)
+    }
+}
+
+impl<I2C: I2c, D: WaitStrategy> Lcd<GroveRgb<I2C>, D> {
+    /**
+    Set the color of the backlight, from 0 (off) to 255 for each LED, shown
+    while the backlight is on.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn set_rgb(&mut self, red: u8, green: u8, blue: u8) -> Result<(), Error<I2C::Error>> {
+        self.bus.set_rgb(red, green, blue).map_err(Error::Bus)
+    }
+}
+
+impl<I2C: I2c, D: WaitStrategy> Lcd<SerLcd<I2C>, D> {
+    /**
+    Set the contrast of the display, lower levels giving more contrast, 120
+    being the default of the board.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn set_contrast(&mut self, level: u8) -> Result<(), Error<I2C::Error>> {
+        self.bus.set_contrast(level).map_err(Error::Bus)?;
+        self.contrast = Some(level);
+        Ok(())
+    }
+
+    /**
+    Set the brightness of the backlight, from 0 (off) to 255, shown while the
+    backlight is on.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn set_brightness(&mut self, level: u8) -> Result<(), Error<I2C::Error>> {
+        self.set_rgb(level, level, level)
+    }
+
+    /**
+    Set the color of the backlight of RGB boards, from 0 (off) to 255 for each
+    LED, shown while the backlight is on.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn set_rgb(&mut self, red: u8, green: u8, blue: u8) -> Result<(), Error<I2C::Error>> {
+        self.bus.set_rgb(red, green, blue).map_err(Error::Bus)
+    }
+}
+
+impl<I2C: I2c, D: WaitStrategy> Lcd<Us2066<I2C>, D> {
+    /**
+    Set the contrast of the OLED, also its brightness, from 0 to 255.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn set_contrast(&mut self, level: u8) -> Result<(), Error<I2C::Error>> {
+        self.bus.set_contrast(level).map_err(Error::Bus)?;
+        self.contrast = Some(level);
+        Ok(())
+    }
+}
+
+impl<I2C: I2c, D: WaitStrategy> Lcd<St7032i<I2C>, D> {
+    /**
+    Set the contrast of the display, from 0 to 255, in 64 steps.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn set_contrast(&mut self, level: u8) -> Result<(), Error<I2C::Error>> {
+        self.bus.set_contrast(level).map_err(Error::Bus)?;
+        self.contrast = Some(level);
+        Ok(())
+    }
+}
+
+impl<I2C: I2c, D: WaitStrategy> Lcd<Mcp23017<I2C>, D> {
+    /**
+    Read which buttons of the RGB LCD shield are held down.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn read_buttons(&mut self) -> Result<Buttons, Error<I2C::Error>> {
+        self.bus.read_buttons().map_err(Error::Bus)
+    }
+}
+
+impl<I2C: I2c, D: WaitStrategy> Lcd<Pcf8575<I2C>, D> {
+    /**
+    Set the pins of the expander port the display is not on, see
+    `Pcf8575::write_other`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn write_other(&mut self, value: u8) -> Result<(), Error<I2C::Error>> {
+        self.bus.write_other(value).map_err(Error::Bus)
+    }
+
+    /**
+    Read the pins of the expander port the display is not on.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn read_other(&mut self) -> Result<u8, Error<I2C::Error>> {
+        self.bus.read_other().map_err(Error::Bus)
+    }
 }
 
-impl<I2C: I2c, D: DelayNs> Lcd<I2C, D> {
-    pub fn new(i2c: I2C, address: u8, delay: D, cols: u8, rows: u8) -> Result<Self, I2C::Error> {
-        let mut display = Self {
-            i2c,
+impl<B: LcdBus, D: WaitStrategy> Lcd<B, D, Uninitialized> {
+    /**
+    Create the driver for a display driven through `bus`. Nothing is sent to
+    the display until `init` is called.
+    */
+    pub fn with_bus(bus: B, delay: D, cols: u8, rows: u8) -> Self {
+        Self {
+            bus,
             control: DisplayControl::new(),
-            address,
+            sleeping: None,
             delay,
+            cols,
             rows,
-            row_offsets: [0x00, 0x40, cols, 0x40 + cols],
-        };
-        display.init()?;
-        Ok(display)
+            row_offsets: RowOffsets::for_cols(cols).0,
+            position: (0, 0),
+            past_left_edge: false,
+            buffer: ShadowBuffer::new(),
+            buffering: false,
+            wrap: false,
+            ellipsis: '.',
+            clear_on_init: true,
+            selected: Controllers::First,
+            font: Dots::Dots5x8,
+            lines: Lines::TwoLine,
+            drop_policy: DropPolicy::Nothing,
+            on_drop: None,
+            glyphs: [None; 8],
+            heartbeat: None,
+            heartbeat_phase: false,
+            charset: Charset::new(),
+            contrast: None,
+            clock: None,
+            ready_at: None,
+            frame_stats: FrameStats::default(),
+            #[cfg(feature = "latency-histogram")]
+            latency: LatencyHistogram::default(),
+            state: PhantomData,
+        }
+    }
+
+    /**
+    Select how many lines the controller drives, `Lines::TwoLine` by default.
+    Genuine one-line modules need `Lines::OneLine`, which makes the display a
+    single row. The common 16x1 modules showing two
+    halves of 8 characters at 0x00 and 0x40 are two-line ones instead, to be
+    set up as 8 columns and 2 rows.
+    */
+    pub fn lines(mut self, lines: Lines) -> Self {
+        self.lines = lines;
+        self
+    }
+
+    /**
+    Select the font, `Dots::Dots5x8` by default. `Dots::Dots5x10` is only
+    available on one-line displays, which the display is then set up as, and
+    leaves room for four custom characters, see `Lcd::create_tall_char`.
+    */
+    pub fn font(mut self, font: Dots) -> Self {
+        self.font = font;
+        self
+    }
+
+    /**
+    Use another DDRAM layout than `RowOffsets::for_cols`, for panels that do
+    not follow it.
+    */
+    pub fn row_offsets(mut self, offsets: RowOffsets) -> Self {
+        self.row_offsets = offsets.0;
+        self
+    }
+
+    /**
+    Whether `init` clears the display, which it does by default. When it does
+    not, a display that stayed powered across a reset of the microcontroller
+    keeps showing its content, and is turned on, instead of blanking until it
+    is repainted. The shadow buffer starts out blank either way.
+    */
+    pub fn clear_on_init(mut self, clear: bool) -> Self {
+        self.clear_on_init = clear;
+        self
+    }
+
+    /**
+    Clear the display and turn the backlight off when the driver is dropped,
+    e.g. when the program exits or panics with unwinding. Same as
+    `set_drop_policy(DropPolicy::Blank)` once initialized.
+    */
+    pub fn blank_on_drop(mut self) -> Self {
+        self.drop_policy = DropPolicy::Blank;
+        self
+    }
+
+    /**
+    Run the HD44780 initialization sequence, turning this driver into one that
+    is ready to print.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn init(self) -> Result<Lcd<B, D>, Error<B::Error>> {
+        self.init_with(|sequence| sequence.standard())
+    }
+
+    /**
+    Initialize the display with a custom sequence, for panels whose datasheet
+    deviates from the HD44780 reference flow. The closure can run the standard
+    steps of the `InitSequence` it is given, interleaved with or replaced by its
+    own commands and delays.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn init_with<F>(self, sequence: F) -> Result<Lcd<B, D>, Error<B::Error>>
+    where
+        F: FnOnce(&mut InitSequence<'_, B, D>) -> Result<(), Error<B::Error>>,
+    {
+        let mut lcd = self.into_state();
+        // A single row once the lines and font are settled, whatever their order
+        if lcd.function() & Lines::TwoLine as u8 == 0 {
+            lcd.rows = 1;
+        }
+        lcd.select(Controllers::Both);
+        sequence(&mut InitSequence::new(&mut lcd))?;
+        // The cursor starts on the first row
+        lcd.select(Controllers::First);
+        // Only a display that came up has something to undo when dropped
+        lcd.set_drop_policy(lcd.drop_policy);
+        Ok(lcd)
+    }
+
+    /**
+    Initialize a display that is still powered and showing valid content, such
+    as after a reset of the microcontroller alone. Function set, display control
+    and entry mode are sent again but the display is not cleared, and is turned on.
+
+    The shadow buffer starts out blank, as the driver cannot know what the
+    display shows.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn reinit_preserving_content(self) -> Result<Lcd<B, D>, Error<B::Error>> {
+        self.clear_on_init(false).init()
     }
+}
 
-    fn init(&mut self) -> Result<(), I2C::Error> {
+impl<B: LcdBus, D: WaitStrategy> Lcd<B, D> {
+    fn wait_for_power_up(&mut self) -> Result<(), Error<B::Error>> {
         //  Set the i2c slave address
         // SEE PAGE 45/46 FOR INITIALIZATION SPECIFICATION!
         // according to datasheet, we need at least 40ms after power rises above 2.7V
         // before sending commands. Arduino can turn on way before 4.5V so we'll wait 50
-        self.delay.delay_ms(50);
+        self.delay.wait_ms(50);
+
+        self.bus
+            .set_backlight(self.control.backlight)
+            .map_err(Error::Bus)?;
+        self.delay.wait_ms(1);
 
-        self.expander_write(self.control.backlight as u8)?;
-        self.delay.delay_ms(1);
+        Ok(())
+    }
 
+    fn enter_4bit_mode(&mut self) -> Result<(), Error<B::Error>> {
         // Send the initial command sequence according to the HD44780 datasheet
         let mode_8bit = Mode::FUNCTIONSET as u8 | BitMode::Bit8 as u8;
-        self.write4bits(mode_8bit)?;
-        self.delay.delay_ms(5);
+        self.write_nibble(mode_8bit)?;
+        self.delay.wait_ms(5);
 
-        self.write4bits(mode_8bit)?;
-        self.delay.delay_ms(5);
+        self.write_nibble(mode_8bit)?;
+        self.delay.wait_ms(5);
 
-        self.write4bits(mode_8bit)?;
-        self.delay.delay_ms(5);
+        self.write_nibble(mode_8bit)?;
+        self.delay.wait_ms(5);
+
+        // 8-bit buses are already in the right mode
+        if let BitMode::Bit8 = self.bus.bit_mode() {
+            return Ok(());
+        }
 
         let mode_4bit = Mode::FUNCTIONSET as u8 | BitMode::Bit4 as u8;
-        self.write4bits(mode_4bit)?;
-        self.delay.delay_ms(5);
+        self.write_nibble(mode_4bit)?;
+        self.delay.wait_ms(5);
 
-        let lines_font = Mode::FUNCTIONSET as u8
-            | BitMode::Bit4 as u8
-            | Dots::Dots5x8 as u8
-            | Lines::TwoLine as u8;
-        self.command(lines_font)?;
+        Ok(())
+    }
 
-        self.clear()?;
+    fn setup(&mut self) -> Result<(), Error<B::Error>> {
+        let function = self.function();
+        self.bus.set_function(function);
+        self.bus.setup(&mut self.delay).map_err(Error::Bus)
+    }
 
-        let entry_mode = Mode::ENTRYMODESET as u8 | Entries::LEFT as u8 | Shift::DECREMENT as u8;
-        self.command(entry_mode)?;
+    fn function_set(&mut self) -> Result<(), Error<B::Error>> {
+        let function = self.function();
+        self.bus.set_function(function);
+        self.command(function)
+    }
 
-        Ok(())
+    // Function set command for the bus width, lines and font
+    fn function(&self) -> u8 {
+        // The controller only has the taller font with a single line
+        let lines = match self.font {
+            Dots::Dots5x8 => self.lines,
+            Dots::Dots5x10 => Lines::OneLine,
+        };
+        Mode::FUNCTIONSET as u8 | self.bus.bit_mode() as u8 | self.font as u8 | lines as u8
+    }
+
+    fn write_entry_mode(&mut self) -> Result<(), Error<B::Error>> {
+        let entry_mode =
+            Mode::ENTRYMODESET as u8 | self.control.entries as u8 | self.control.shift as u8;
+        self.command(entry_mode)
     }
 
     /********** high level commands, for the user! */
@@ -236,11 +836,15 @@ impl<I2C: I2c, D: DelayNs> Lcd<I2C, D> {
 
     # Errors
 
-    Returns a `Result` that will report I2C errors, if any.
+    Returns a `Result` that will report bus errors, if any.
     */
-    pub fn clear(&mut self) -> Result<(), I2C::Error> {
+    pub fn clear(&mut self) -> Result<(), Error<B::Error>> {
         self.command(Mode::CLEARDISPLAY as u8)?;
-        self.delay.delay_ms(2);
+        self.wait_for_long_command();
+        self.position = (0, 0);
+        self.past_left_edge = false;
+        self.select(Controllers::First);
+        self.buffer.clear();
         Ok(())
     }
 
@@ -249,22 +853,26 @@ impl<I2C: I2c, D: DelayNs> Lcd<I2C, D> {
 
     # Errors
 
-    Returns a `Result` that will report I2C errors, if any.
+    Returns a `Result` that will report bus errors, if any.
     */
-    pub fn home(&mut self) -> Result<(), I2C::Error> {
+    pub fn home(&mut self) -> Result<(), Error<B::Error>> {
         self.command(Mode::RETURNHOME as u8)?;
-        self.delay.delay_ms(2);
+        self.wait_for_long_command();
+        self.position = (0, 0);
+        self.past_left_edge = false;
+        self.select(Controllers::First);
         Ok(())
     }
 
     /**
-    Set the position of the cursor
+    Set the position of the cursor. When buffering, the cursor of the display
+    only moves on the next `flush`.
 
     # Errors
 
-    Returns a `Result` that will report I2C errors, if any.
+    Returns a `Result` that will report bus errors, if any.
     */
-    pub fn set_cursor_position(&mut self, col: u8, mut row: u8) -> Result<(), I2C::Error> {
+    pub fn set_cursor_position(&mut self, col: u8, mut row: u8) -> Result<(), Error<B::Error>> {
         let max_rows = self.row_offsets.len() as u8;
         // // Code based of LiquidCrystal arudino library
         if row >= max_rows {
@@ -275,129 +883,1695 @@ impl<I2C: I2c, D: DelayNs> Lcd<I2C, D> {
             row = self.rows - 1;
         }
 
-        self.command(Mode::SETDDRAMADDR as u8 | (col + self.row_offsets[row as usize]))?;
+        if !self.buffering {
+            self.set_ddram_address(col, row)?;
+        }
+        self.position = (col, row);
+        self.past_left_edge = false;
         Ok(())
     }
+
     /**
-    Create custom character at CGRAM location (0 - 7)
+    Set the position of the cursor, refusing positions outside the display
+    instead of clamping them as `set_cursor_position` does.
 
     # Errors
 
-    Returns a `Result` that will report I2C errors, if any.
-     */
-    pub fn create_char(&mut self, location: u8, charmap: [u8; 8]) {
-        let location = location & 0x7;
-        let _ = self.command(Mode::SETCGRAMADDR as u8 | (location << 3));
-
-        for item in &charmap {
-            let _ = self.write(*item);
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::InvalidPosition` if `col` or `row` is outside the display. The
+    cursor does not move in that case.
+    */
+    pub fn try_set_cursor_position(&mut self, col: u8, row: u8) -> Result<(), Error<B::Error>> {
+        if col >= self.cols || row >= self.rows {
+            return Err(Error::InvalidPosition);
         }
+        self.set_cursor_position(col, row)
     }
+
     /**
-    Control whether the display is on or off
+    Write a single character at the given position, then move the cursor back to
+    where it was before the call.
 
     # Errors
 
-    Returns a `Result` that will report I2C errors, if any.
+    Returns a `Result` that will report bus errors, if any.
     */
-    pub fn set_display(&mut self, display: Display) -> Result<(), I2C::Error> {
-        self.control.display = display;
-        self.write_display_control()
+    pub fn set_char_at(&mut self, col: u8, row: u8, c: char) -> Result<(), Error<B::Error>> {
+        let (prev_col, prev_row) = self.position;
+        self.set_cursor_position(col, row)?;
+        self.write(self.encode_char(c))?;
+        self.set_cursor_position(prev_col, prev_row)
     }
 
-    /**
-    Sets the visibility of the cursor, which is a non-blinking _
+    /// A `core::fmt::Write` handle that writes to `row` only, starting at its
+    /// first column. See `RowWriter`.
+    pub fn row_writer(&mut self, row: u8) -> RowWriter<'_, B, D> {
+        RowWriter::new(self, row)
+    }
 
-    # Errors
+    /// Select the character ROM of the display, which sets how chars are printed.
+    /// Defaults to `RomVariant::A00`.
+    pub fn set_rom_variant(&mut self, rom: RomVariant) {
+        self.charset.rom = rom;
+    }
 
-    Returns a `Result` that will report I2C errors, if any.
-    */
-    pub fn set_cursor(&mut self, cursor: Cursor) -> Result<(), I2C::Error> {
-        self.control.cursor = cursor;
-        self.write_display_control()
+    pub fn rom_variant(&self) -> RomVariant {
+        self.charset.rom
     }
 
     /**
-    Turns on the blinking block cursor
+    Find out the character ROM of the display with a visual test, and select it.
+
+    Reading back DDRAM only returns the codes that were written, not the glyphs
+    they show, so the ROM cannot be told apart without looking at the panel.
+    The first row shows the glyph at 0x5C, which is `¥` on the A00 ROM and `\`
+    on the A02 one, then `shows_yen` is called to ask the user, e.g. with a
+    button. The screen is cleared afterwards.
 
     # Errors
 
-    Returns a `Result` that will report I2C errors, if any.
+    Returns a `Result` that will report bus errors, if any.
     */
-    pub fn set_blink(&mut self, blink: Blink) -> Result<(), I2C::Error> {
-        self.control.blink = blink;
-        self.write_display_control()
+    pub fn detect_rom_variant<F: FnOnce() -> bool>(
+        &mut self,
+        shows_yen: F,
+    ) -> Result<RomVariant, Error<B::Error>> {
+        self.clear()?;
+        for code in b"Yen? \x5c" {
+            self.write(*code)?;
+        }
+        // The glyph has to be on the screen to be looked at, even when buffering
+        self.flush()?;
+
+        let rom = if shows_yen() {
+            RomVariant::A00
+        } else {
+            RomVariant::A02
+        };
+        self.set_rom_variant(rom);
+
+        self.clear()?;
+        Ok(rom)
     }
 
-    pub fn set_backlight(&mut self, backlight: Backlight) -> Result<(), I2C::Error> {
-        self.control.backlight = backlight;
-        self.expander_write(0)
+    /// Set the ROM code or CGRAM location (0 - 7) printed in place of chars the
+    /// ROM does not have. Defaults to `?`.
+    pub fn set_replacement_char(&mut self, code: u8) {
+        self.charset.replacement = code;
     }
 
-    /*********** mid level commands, for sending data/cmds */
+    /// Set the char `print_truncated` shows in the last column when the text is
+    /// cut. Defaults to `.`, as neither ROM has `…`, which can be loaded with
+    /// `load_glyphs` instead.
+    pub fn set_ellipsis(&mut self, c: char) {
+        self.ellipsis = c;
+    }
 
     /**
-    Adds a string to the current position. The cursor will advance
-    after this call to the next column
+    Upload up to eight custom characters to CGRAM locations 0 and up, and print
+    them in place of their char from then on, e.g. `é` on the A00 ROM. Chars
+    registered by an earlier call are forgotten, and a location stops standing
+    for its char when another character is uploaded to it.
+
     # Errors
-    Returns a `Result` that will report I2C errors, if any.
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::CgramFull` if there are more than eight glyphs. Nothing is uploaded
+    in that case.
     */
-    pub fn print(&mut self, s: &str) -> Result<(), I2C::Error> {
-        let mut row = 0;
-        for c in s.chars() {
-            if c == '\n' {
-                row = (row + 1).clamp(1, self.rows);
-                self.set_cursor_position(0, row)?;
-            } else {
-                self.write(c as u8)?;
-            }
+    pub fn load_glyphs(&mut self, glyphs: &[(char, [u8; 8])]) -> Result<(), Error<B::Error>> {
+        if glyphs.len() > self.glyphs.len() {
+            return Err(Error::CgramFull);
         }
 
+        self.charset.custom = [None; 8];
+        for (location, (c, charmap)) in glyphs.iter().enumerate() {
+            self.upload_char(location as u8, *charmap)?;
+            self.charset.custom[location] = Some(*c);
+        }
         Ok(())
     }
 
-    // Send two bytes to the display
-    pub fn write(&mut self, value: u8) -> Result<(), I2C::Error> {
-        self.send(value, BitAction::RegisterSelect)
-    }
+    /**
+    Create custom character at CGRAM location (0 - 7)
 
-    // Set one of the display's control options and then send the updated set of options to the display
-    fn write_display_control(&mut self) -> Result<(), I2C::Error> {
-        self.command(Mode::DISPLAYCONTROL as u8 | self.control.value())
-    }
+    # Errors
 
-    fn command(&mut self, value: u8) -> Result<(), I2C::Error> {
-        self.send(value, BitAction::Command)
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::InvalidCgramSlot` if `location` is above 7.
+    */
+    pub fn create_char(&mut self, location: u8, charmap: [u8; 8]) -> Result<(), Error<B::Error>> {
+        if location as usize >= self.glyphs.len() {
+            return Err(Error::InvalidCgramSlot);
+        }
+        self.upload_char(location, charmap)
     }
 
-    /************ low level data pushing commands **********/
+    /**
+    Create a custom character of the 5x10 font, see `font`, at location (0 -
+    3), shown by writing code `2 * location`. It takes the CGRAM space
+    of two 5x8 ones, locations `2 * location` and `2 * location + 1`.
 
-    fn send(&mut self, data: u8, mode: BitAction) -> Result<(), I2C::Error> {
-        let high_bits: u8 = data & 0xf0;
-        let low_bits: u8 = (data << 4) & 0xf0;
-        self.write4bits(high_bits | mode as u8)?;
-        self.write4bits(low_bits | mode as u8)?;
-        Ok(())
-    }
+    # Errors
 
-    fn write4bits(&mut self, value: u8) -> Result<(), I2C::Error> {
-        self.expander_write(value)?;
-        self.pulse_enable(value)?;
-        Ok(())
-    }
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::InvalidCgramSlot` if `location` is above 3.
+    */
+    pub fn create_tall_char(
+        &mut self,
+        location: u8,
+        charmap: [u8; 10],
+    ) -> Result<(), Error<B::Error>> {
+        if location as usize >= self.glyphs.len() / 2 {
+            return Err(Error::InvalidCgramSlot);
+        }
 
-    fn expander_write(&mut self, data: u8) -> Result<(), I2C::Error> {
-        self.i2c
-            .write(self.address, &[data | self.control.backlight as u8])
+        // Each tall glyph spans 16 bytes of CGRAM, the last ones unused
+        let mut top = [0; 8];
+        let mut bottom = [0; 8];
+        top.copy_from_slice(&charmap[..8]);
+        bottom[..2].copy_from_slice(&charmap[8..]);
+        self.upload_char(2 * location, top)?;
+        self.upload_char(2 * location + 1, bottom)
     }
 
-    fn pulse_enable(&mut self, data: u8) -> Result<(), I2C::Error> {
-        self.expander_write(data | BitAction::Enable as u8)?; // En high
-        self.delay.delay_us(1);
+    /**
+    Control whether the display is on or off
+
+    # Errors
 
-        self.expander_write(data & !(BitAction::Enable as u8))?; // En low
-        self.delay.delay_us(1);
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn set_display(&mut self, display: Display) -> Result<(), Error<B::Error>> {
+        self.control.display = display;
+        self.write_display_control()
+    }
 
-        Ok(())
+    /**
+    Sets the visibility of the cursor, which is a non-blinking _
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn set_cursor(&mut self, cursor: Cursor) -> Result<(), Error<B::Error>> {
+        self.control.cursor = cursor;
+        self.write_display_control()
+    }
+
+    /**
+    Turns on the blinking block cursor
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn set_blink(&mut self, blink: Blink) -> Result<(), Error<B::Error>> {
+        self.control.blink = blink;
+        self.write_display_control()
+    }
+
+    /**
+    Set the entry mode: the direction the cursor moves in after each character,
+    see `set_text_direction`, and whether the display shifts on each write,
+    `Shift::INCREMENT`, see `set_autoscroll`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn set_entry_mode(
+        &mut self,
+        entries: Entries,
+        shift: Shift,
+    ) -> Result<(), Error<B::Error>> {
+        self.control.entries = entries;
+        self.control.shift = shift;
+        self.write_entry_mode()
+    }
+
+    /**
+    Turn autoscroll on or off. With autoscroll, the whole display shifts by one
+    column on each write, so new text pushes the existing content aside while
+    the cursor seems to stay in place.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn set_autoscroll(&mut self, autoscroll: bool) -> Result<(), Error<B::Error>> {
+        self.control.shift = if autoscroll {
+            Shift::INCREMENT
+        } else {
+            Shift::DECREMENT
+        };
+        self.write_entry_mode()
+    }
+
+    /**
+    Make `print` continue at the start of the next row when it reaches
+    the end of a row, instead of writing to the DDRAM past the edge of the
+    display, which is not shown. The last row is followed by the first one.
+    Off by default.
+    */
+    pub fn set_line_wrap(&mut self, wrap: bool) {
+        self.wrap = wrap;
+    }
+
+    /**
+    Set the direction text is written in: `Entries::LEFT` moves the cursor
+    right after each character, for left to right text, `Entries::RIGHT` moves
+    it left.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn set_text_direction(&mut self, entries: Entries) -> Result<(), Error<B::Error>> {
+        self.control.entries = entries;
+        self.write_entry_mode()?;
+
+        // Writing left to right again starts from the first column
+        if let (Entries::LEFT, true) = (entries, self.past_left_edge) {
+            let (col, row) = self.position;
+            self.set_cursor_position(col, row)?;
+        }
+        Ok(())
+    }
+
+    /**
+    Write text from left to right, as after initialization.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn left_to_right(&mut self) -> Result<(), Error<B::Error>> {
+        self.set_text_direction(Entries::LEFT)
+    }
+
+    /**
+    Write text from right to left.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn right_to_left(&mut self) -> Result<(), Error<B::Error>> {
+        self.set_text_direction(Entries::RIGHT)
+    }
+
+    /**
+    Shift the content of every row one column to the left, without rewriting
+    it. The cursor moves along with the content.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn scroll_display_left(&mut self) -> Result<(), Error<B::Error>> {
+        self.shift_display(Direction::LEFT)
+    }
+
+    /**
+    Shift the content of every row one column to the right, without rewriting
+    it. The cursor moves along with the content.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn scroll_display_right(&mut self) -> Result<(), Error<B::Error>> {
+        self.shift_display(Direction::RIGHT)
+    }
+
+    /**
+    Move the cursor one column to the left without writing anything. The
+    cursor stays put on the first column. When buffering, the cursor of the
+    display only moves on the next `flush`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn move_cursor_left(&mut self) -> Result<(), Error<B::Error>> {
+        let (col, row) = self.position;
+        if col == 0 || self.past_left_edge {
+            return Ok(());
+        }
+        if !self.buffering {
+            self.shift_cursor(Direction::LEFT)?;
+        }
+        self.position = (col - 1, row);
+        Ok(())
+    }
+
+    /**
+    Move the cursor one column to the right without writing anything. The
+    cursor stays put on the last column. When buffering, the cursor of the
+    display only moves on the next `flush`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn move_cursor_right(&mut self) -> Result<(), Error<B::Error>> {
+        let (col, row) = self.position;
+        if self.past_left_edge {
+            // Back onto the first column
+            return self.set_cursor_position(col, row);
+        }
+        if col + 1 >= self.cols {
+            return Ok(());
+        }
+        if !self.buffering {
+            self.shift_cursor(Direction::RIGHT)?;
+        }
+        self.position = (col + 1, row);
+        Ok(())
+    }
+
+    /**
+    Erase the character before the cursor and move the cursor onto it. On the
+    first column, this erases the last character of the previous row, and does
+    nothing on the first cell of the display.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn backspace(&mut self) -> Result<(), Error<B::Error>> {
+        let (col, row) = match self.position {
+            (0, 0) => return Ok(()),
+            (0, row) => (self.cols - 1, row - 1),
+            (col, row) => (col - 1, row),
+        };
+
+        self.set_cursor_position(col, row)?;
+        self.write(b' ')?;
+        self.set_cursor_position(col, row)
+    }
+
+    pub fn set_backlight(&mut self, backlight: Backlight) -> Result<(), Error<B::Error>> {
+        self.control.backlight = backlight;
+        self.bus.set_backlight(backlight).map_err(Error::Bus)
+    }
+
+    /**
+    Run the standard initialization sequence again, e.g. after a brown-out left
+    the controller reset or out of step with the driver, then restore the
+    display control settings, the entry mode, the custom characters and, from
+    the shadow buffer, the content and the cursor. When buffering, the content
+    is only restored on the next `flush`.
+
+    The sequence works whether the controller is in 4-bit or 8-bit mode. Steps
+    added with `init_with` are not run again.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn reinitialize(&mut self) -> Result<(), Error<B::Error>> {
+        let selected = self.selected;
+        self.select(Controllers::Both);
+        self.wait_for_power_up()?;
+        self.enter_4bit_mode()?;
+        self.setup()?;
+        self.function_set()?;
+        self.command(Mode::CLEARDISPLAY as u8)?;
+        self.wait_for_long_command();
+        self.write_display_control()?;
+        self.write_entry_mode()?;
+        self.select(selected);
+
+        // Uploading forgets which chars the glyphs stand for
+        let custom = self.charset.custom;
+        for (location, glyph) in self.glyphs.into_iter().enumerate() {
+            if let Some(charmap) = glyph {
+                self.upload_char(location as u8, charmap)?;
+            }
+        }
+        self.charset.custom = custom;
+
+        self.buffer.invalidate();
+        self.flush_unless_buffering()?;
+        let (col, row) = self.position;
+        self.set_ddram_address(col, row)
+    }
+
+    /**
+    Check that the display still answers, see `LcdBus::check`, which on a
+    backpack writes the current state of the expander lines again, that the
+    display ignores as the enable line stays low. Returns `false` if the
+    write failed for any reason, e.g. no ACK from an unplugged backpack, in
+    which case `reinitialize` may bring it back.
+    */
+    pub fn health_check(&mut self) -> bool {
+        self.bus.check(self.control.backlight).is_ok()
+    }
+
+    /**
+    Turn the display and the backlight off, keeping DDRAM and CGRAM, until
+    `wake` is called. Does nothing if already asleep.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn sleep(&mut self) -> Result<(), Error<B::Error>> {
+        if self.sleeping.is_some() {
+            return Ok(());
+        }
+
+        self.sleeping = Some(self.control);
+        self.control.display = Display::Off;
+        self.control.backlight = Backlight::Off;
+        self.write_display_control()?;
+        self.bus.set_backlight(Backlight::Off).map_err(Error::Bus)
+    }
+
+    /**
+    Restore the display, cursor and backlight settings from before `sleep`.
+    Does nothing if not asleep.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn wake(&mut self) -> Result<(), Error<B::Error>> {
+        let Some(control) = self.sleeping.take() else {
+            return Ok(());
+        };
+        self.control = control;
+        self.write_display_control()?;
+        self.bus
+            .set_backlight(self.control.backlight)
+            .map_err(Error::Bus)
+    }
+
+    /**
+    Read the address counter of the controller, the DDRAM address the next
+    character is written to, or the CGRAM one right after uploading a custom
+    character. Needs the R/W pin of the display to be wired to the expander,
+    as on most backpacks.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn read_address_counter(&mut self) -> Result<u8, Error<B::Error>> {
+        Ok(self.read_byte(Register::Command)? & 0x7f)
+    }
+
+    /**
+    Read back the character shown at `col`, `row` from the DDRAM of the
+    display, unlike `char_at` which looks at the shadow buffer. Needs the R/W
+    pin to be wired, like `read_address_counter`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::InvalidPosition` if the position is outside the display.
+    */
+    pub fn read_char_at(&mut self, col: u8, row: u8) -> Result<char, Error<B::Error>> {
+        let mut code = [0];
+        self.read_ddram(col, row, &mut code)?;
+        Ok(self.decode_char(code[0]))
+    }
+
+    /**
+    Read back the ROM codes of `row` from the DDRAM of the display into `buf`,
+    from the first column, and return how many were read: the width of the
+    display, or the length of `buf` if shorter. Needs the R/W pin to be wired.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::InvalidPosition` if the row is outside the display.
+    */
+    pub fn read_row(&mut self, row: u8, buf: &mut [u8]) -> Result<usize, Error<B::Error>> {
+        let len = buf.len().min(self.cols as usize);
+        self.read_ddram(0, row, &mut buf[..len])?;
+        Ok(len)
+    }
+
+    /**
+    Read back the custom character at CGRAM location (0 - 7), e.g. to check an
+    upload. Only the five low bits of each row are part of the glyph, the
+    others are cleared. Needs the R/W pin to be wired.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::InvalidCgramSlot` if `location` is above 7.
+    */
+    pub fn read_char(&mut self, location: u8) -> Result<[u8; 8], Error<B::Error>> {
+        if location as usize >= self.glyphs.len() {
+            return Err(Error::InvalidCgramSlot);
+        }
+
+        let rtl = matches!(self.control.entries, Entries::RIGHT);
+        let mut charmap = [0; 8];
+        for (i, line) in charmap.iter_mut().enumerate() {
+            if i == 0 || rtl {
+                self.command(Mode::SETCGRAMADDR as u8 | (location << 3) | i as u8)?;
+            }
+            *line = self.read_byte(Register::Data)? & 0x1f;
+        }
+
+        let (col, row) = self.position;
+        self.set_ddram_address(col, row)?;
+        Ok(charmap)
+    }
+
+    // Read consecutive DDRAM cells from `col`, `row`, then point the address
+    // counter back at the cursor
+    fn read_ddram(&mut self, col: u8, row: u8, buf: &mut [u8]) -> Result<(), Error<B::Error>> {
+        if col >= self.cols || row >= self.rows {
+            return Err(Error::InvalidPosition);
+        }
+
+        // The address counter moves on after each read, to the left when
+        // writing right to left
+        let rtl = matches!(self.control.entries, Entries::RIGHT);
+        for (i, code) in buf.iter_mut().enumerate() {
+            if i == 0 || rtl {
+                self.set_ddram_address(col + i as u8, row)?;
+            }
+            *code = self.read_byte(Register::Data)?;
+        }
+
+        let (col, row) = self.position;
+        self.set_ddram_address(col, row)
+    }
+
+    /// Whether `sleep` was called without `wake` since
+    pub fn is_asleep(&self) -> bool {
+        self.sleeping.is_some()
+    }
+
+    /**
+    Flash the backlight `count` times, e.g. for an alarm, then restore it. Each
+    flash inverts the backlight for half of `period_ms` and restores it for the
+    other half. Blocks until done.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn blink_backlight(&mut self, count: u32, period_ms: u32) -> Result<(), Error<B::Error>> {
+        let previous = self.control.backlight;
+        let inverted = match previous {
+            Backlight::On => Backlight::Off,
+            Backlight::Off => Backlight::On,
+        };
+
+        for _ in 0..count {
+            self.set_backlight(inverted)?;
+            self.delay.wait_ms(period_ms / 2);
+            self.set_backlight(previous)?;
+            self.delay.wait_ms(period_ms - period_ms / 2);
+        }
+        Ok(())
+    }
+
+    /// Set what is done to the display when the driver is dropped, `Nothing`
+    /// by default. Errors while dropping are ignored.
+    pub fn set_drop_policy(&mut self, policy: DropPolicy) {
+        self.drop_policy = policy;
+        self.on_drop = match policy {
+            DropPolicy::Nothing => None,
+            _ => Some(Self::apply_drop_policy),
+        };
+    }
+
+    fn apply_drop_policy(&mut self) {
+        let _ = match self.drop_policy {
+            DropPolicy::Nothing => Ok(()),
+            DropPolicy::BacklightOff => self.set_backlight(Backlight::Off),
+            DropPolicy::DisplayOff => self.set_display(Display::Off),
+            DropPolicy::Clear => self.clear(),
+            DropPolicy::Blank => {
+                // Still turn the backlight off if clearing failed
+                let _ = self.clear();
+                self.set_backlight(Backlight::Off)
+            }
+        };
+    }
+
+    /// Number of columns of the display
+    pub fn cols(&self) -> u8 {
+        self.cols
+    }
+
+    /// Number of rows of the display
+    pub fn rows(&self) -> u8 {
+        self.rows
+    }
+
+    /// Columns and rows of the display
+    pub fn geometry(&self) -> (u8, u8) {
+        (self.cols, self.rows)
+    }
+
+    /// Column and row the next character will be written to, as tracked by the
+    /// driver across writes and cursor moves
+    pub fn cursor_position(&self) -> (u8, u8) {
+        self.position
+    }
+
+    /// Whether the backlight is on, as last set
+    pub fn backlight(&self) -> Backlight {
+        self.control.backlight
+    }
+
+    /// Whether the display is on, as last set
+    pub fn display(&self) -> Display {
+        self.control.display
+    }
+
+    /// Whether the underline cursor is shown, as last set
+    pub fn cursor(&self) -> Cursor {
+        self.control.cursor
+    }
+
+    /// Whether the block cursor blinks, as last set
+    pub fn blink(&self) -> Blink {
+        self.control.blink
+    }
+
+    /// Copy of every display control setting, including the entry mode
+    pub fn control(&self) -> DisplayControl {
+        self.control
+    }
+
+    /// Current display settings, including the custom characters uploaded so
+    /// far, the chars they stand for, the character ROM and the contrast
+    pub fn settings(&self) -> Settings {
+        Settings {
+            display: self.control.display,
+            cursor: self.control.cursor,
+            blink: self.control.blink,
+            backlight: self.control.backlight,
+            glyphs: self.glyphs,
+            custom: self.charset.custom,
+            rom: self.charset.rom,
+            replacement: self.charset.replacement,
+            contrast: self.contrast,
+        }
+    }
+
+    /**
+    Apply display settings, uploading their custom characters. The contrast is
+    only set on controllers having a setting for it, see
+    `LcdBus::restore_contrast`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn apply_settings(&mut self, settings: &Settings) -> Result<(), Error<B::Error>> {
+        for (location, glyph) in settings.glyphs.iter().enumerate() {
+            if let Some(charmap) = glyph {
+                self.upload_char(location as u8, *charmap)?;
+            }
+        }
+        // Uploading forgets which chars the glyphs stand for
+        self.charset.custom = settings.custom;
+        self.charset.rom = settings.rom;
+        self.charset.replacement = settings.replacement;
+
+        if let Some(level) = settings.contrast {
+            if self.bus.restore_contrast(level).map_err(Error::Bus)? {
+                self.contrast = Some(level);
+            }
+        }
+
+        self.control.display = settings.display;
+        self.control.cursor = settings.cursor;
+        self.control.blink = settings.blink;
+        self.control.backlight = settings.backlight;
+        self.write_display_control()?;
+        self.bus
+            .set_backlight(self.control.backlight)
+            .map_err(Error::Bus)
+    }
+
+    /**
+    Save the current display settings at `offset` in `storage`, taking
+    `Settings::SIZE` bytes.
+
+    # Errors
+
+    Returns a `Result` that will report storage errors, if any.
+    */
+    #[cfg(feature = "embedded-storage")]
+    pub fn save_settings<S: embedded_storage::Storage>(
+        &self,
+        storage: &mut S,
+        offset: u32,
+    ) -> Result<(), S::Error> {
+        storage.write(offset, &self.settings().to_bytes())
+    }
+
+    /**
+    Restore display settings saved with `save_settings` at `offset` in `storage`.
+
+    # Errors
+
+    Returns a `Result` that will report driver and storage errors, if any, or
+    `SettingsError::Invalid` if no settings were saved there.
+    */
+    #[cfg(feature = "embedded-storage")]
+    pub fn load_settings<S: embedded_storage::ReadStorage>(
+        &mut self,
+        storage: &mut S,
+        offset: u32,
+    ) -> Result<(), SettingsError<Error<B::Error>, S::Error>> {
+        let mut bytes = [0; Settings::SIZE];
+        storage
+            .read(offset, &mut bytes)
+            .map_err(SettingsError::Storage)?;
+        let settings = Settings::from_bytes(&bytes).ok_or(SettingsError::Invalid)?;
+        self.apply_settings(&settings).map_err(SettingsError::Lcd)
+    }
+
+    /**
+    Enable or disable buffering. While buffering, writes only update the shadow
+    buffer and reach the display on `flush`, which sends just the cells that
+    changed. Disabling buffering flushes pending changes first.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn set_buffering(&mut self, buffering: bool) -> Result<(), Error<B::Error>> {
+        if !buffering {
+            self.flush()?;
+        }
+        self.buffering = buffering;
+        Ok(())
+    }
+
+    /**
+    Send every cell changed since the last flush to the display, then move the
+    display's cursor to the tracked cursor position.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any. Cells that could not
+    be sent stay pending for the next flush.
+    */
+    pub fn flush(&mut self) -> Result<(), Error<B::Error>> {
+        let started = self.clock.map(|clock| clock());
+        let bus_bytes = self.bus.bytes_written();
+        let mut cells = 0;
+
+        // Where the address counter of the display points at
+        let mut address = None;
+        self.send_dirty(u32::MAX, &mut address, &mut cells)?;
+
+        if self.beat()? {
+            address = self
+                .heartbeat
+                .map(|heartbeat| (heartbeat.col + 1, heartbeat.row));
+            cells += 1;
+        }
+
+        if address.is_some() {
+            let (col, row) = self.position;
+            self.set_ddram_address(col, row)?;
+        }
+
+        self.frame_stats.record(FlushStats {
+            cells,
+            bytes: self.bus.bytes_written().wrapping_sub(bus_bytes),
+            micros: self
+                .clock
+                .zip(started)
+                .map(|(clock, started)| clock().wrapping_sub(started)),
+        });
+
+        Ok(())
+    }
+
+    /**
+    Send the cells changed since the last flush, like `flush`, but stop before
+    writing more than `max_bytes` bytes to the bus, so a large update can be
+    spread over several calls of bounded duration. Each call carries on with
+    the first cell still pending, in row order.
+
+    A cell takes `LcdBus::command_bytes`, 6 bytes on a `Pcf8574`, moving the
+    address counter as many more, and moving the cursor back at the end as
+    many more. Busy flag polling is not counted. The heartbeat and the frame
+    statistics are left to `flush`.
+
+    At least one cell is sent by each call, so a budget below three times
+    `command_bytes`, the cost of a single cell, still makes progress, writing
+    more than `max_bytes` bytes.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any. Cells that could not
+    be sent stay pending.
+    */
+    pub fn flush_chunk(&mut self, max_bytes: u32) -> Result<Progress, Error<B::Error>> {
+        let mut address = None;
+        let mut cells = 0;
+        // Keep room to move the cursor back
+        let done = self.send_dirty(
+            max_bytes.saturating_sub(self.bus.command_bytes()),
+            &mut address,
+            &mut cells,
+        )?;
+
+        if address.is_some() {
+            let (col, row) = self.position;
+            self.set_ddram_address(col, row)?;
+        }
+
+        Ok(if done {
+            Progress::Done { cells }
+        } else {
+            Progress::Pending { cells }
+        })
+    }
+
+    /**
+    Stage `s` at the cursor, as `print` does while buffering, for `poll` to
+    send without blocking.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, which only happen
+    if the cursor needs to be moved while not buffering.
+    */
+    pub fn start_print(&mut self, s: &str) -> Result<(), Error<B::Error>> {
+        let buffering = self.buffering;
+        self.buffering = true;
+        let staged = self.print(s);
+        self.buffering = buffering;
+        staged
+    }
+
+    /**
+    Stage spaces over the whole screen and move the cursor home, for `poll` to
+    send, instead of the clear command that keeps the controller busy for
+    1.52ms.
+    */
+    pub fn start_clear(&mut self) {
+        for row in 0..self.rows.min(buffer::MAX_ROWS as u8) {
+            for col in 0..self.cols.min(buffer::MAX_COLS as u8) {
+                self.buffer.stage(col, row, b' ');
+            }
+        }
+        self.position = (0, 0);
+        self.past_left_edge = false;
+    }
+
+    /**
+    Send the next pending cell, staged by `start_print`, `start_clear` or while
+    buffering, for superloops that cannot wait. `now_us` is the time from a
+    monotonic microsecond clock, which may wrap around: calls less than 50µs
+    apart, about the time the controller takes to write a character, do
+    nothing. After each cell sent, the cursor is moved back to the tracked
+    position, so that prints between calls land where they should.
+
+    # Errors
+
+    Returns `Error::WouldBlock` while cells are pending, in which case `poll`
+    should be called again later, or bus errors, if any.
+    */
+    pub fn poll(&mut self, now_us: u32) -> Result<(), Error<B::Error>> {
+        const CELL_US: u32 = 50;
+
+        if let Some(ready_at) = self.ready_at {
+            if (now_us.wrapping_sub(ready_at) as i32) < 0 {
+                return Err(Error::WouldBlock);
+            }
+        }
+
+        // Other writes may have moved the address counter since the last call,
+        // so the cell is sent along with its address
+        let mut address = None;
+        let mut cells = 0;
+        let done = self.send_dirty(2 * self.bus.command_bytes(), &mut address, &mut cells)?;
+        if address.is_some() {
+            let (col, row) = self.position;
+            self.set_ddram_address(col, row)?;
+        }
+
+        if !done {
+            self.ready_at = Some(now_us.wrapping_add(CELL_US));
+            return Err(Error::WouldBlock);
+        }
+        self.ready_at = None;
+        Ok(())
+    }
+
+    // Send dirty cells in row order while they fit in `max_bytes` bytes of the
+    // bus, returning whether every dirty cell was sent
+    fn send_dirty(
+        &mut self,
+        max_bytes: u32,
+        address: &mut Option<(u8, u8)>,
+        cells: &mut u32,
+    ) -> Result<bool, Error<B::Error>> {
+        let mut budget = max_bytes;
+        let command = self.bus.command_bytes();
+
+        for row in 0..self.rows.min(buffer::MAX_ROWS as u8) {
+            for col in 0..self.cols.min(buffer::MAX_COLS as u8) {
+                let Some(value) = self.buffer.dirty(col, row) else {
+                    continue;
+                };
+
+                let moves = *address != Some((col, row));
+                let cost = if moves { 2 * command } else { command };
+                // Always send a cell, so that a small budget still makes progress
+                if cost > budget && *cells > 0 {
+                    return Ok(false);
+                }
+                budget = budget.saturating_sub(cost);
+
+                if moves {
+                    self.set_ddram_address(col, row)?;
+                }
+                self.send(value, Register::Data)?;
+                self.buffer.mark_clean(col, row);
+                *address = self.next_col(col).map(|col| (col, row));
+                *cells += 1;
+            }
+        }
+        Ok(true)
+    }
+
+    /**
+    Set the cell toggled on every `flush` and `tick`, or `None` to stop toggling.
+    */
+    pub fn set_heartbeat(&mut self, heartbeat: Option<Heartbeat>) {
+        self.heartbeat = heartbeat;
+    }
+
+    /**
+    Toggle the heartbeat cell, if any. It is written to the display right away,
+    even when buffering.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn tick(&mut self) -> Result<(), Error<B::Error>> {
+        if self.beat()? {
+            let (col, row) = self.position;
+            self.set_ddram_address(col, row)?;
+        }
+        Ok(())
+    }
+
+    // Show the other heartbeat glyph, leaving the address counter after it.
+    // Returns whether there was a heartbeat cell to toggle.
+    fn beat(&mut self) -> Result<bool, Error<B::Error>> {
+        let Some(heartbeat) = self.heartbeat else {
+            return Ok(false);
+        };
+        if heartbeat.col >= self.cols || heartbeat.row >= self.rows {
+            return Ok(false);
+        }
+
+        let phase = !self.heartbeat_phase;
+        let code = heartbeat.glyphs[phase as usize];
+        self.set_ddram_address(heartbeat.col, heartbeat.row)?;
+        self.send(code, Register::Data)?;
+        self.buffer.store(heartbeat.col, heartbeat.row, code);
+        self.heartbeat_phase = phase;
+
+        Ok(true)
+    }
+
+    /**
+    Provide a monotonic clock returning microseconds, used to time flushes.
+    The value may wrap around.
+    */
+    pub fn set_clock(&mut self, clock: fn() -> u32) {
+        self.clock = Some(clock);
+    }
+
+    /// Metrics of the last flushes
+    pub fn frame_stats(&self) -> &FrameStats {
+        &self.frame_stats
+    }
+
+    /// Start collecting flush metrics from scratch
+    pub fn reset_frame_stats(&mut self) {
+        self.frame_stats = FrameStats::default();
+    }
+
+    /// Latency of the I2C writes, recorded only when a clock is provided with `set_clock`
+    #[cfg(feature = "latency-histogram")]
+    pub fn latency_histogram(&self) -> &LatencyHistogram {
+        &self.latency
+    }
+
+    /// Start recording I2C latency from scratch
+    #[cfg(feature = "latency-histogram")]
+    pub fn reset_latency_histogram(&mut self) {
+        self.latency = LatencyHistogram::default();
+    }
+
+    /**
+    Character shown at the given position, according to the shadow buffer.
+    Returns `None` if the position is outside the display.
+    */
+    pub fn char_at(&self, col: u8, row: u8) -> Option<char> {
+        if col >= self.cols || row >= self.rows {
+            return None;
+        }
+        self.buffer.get(col, row).map(|code| self.decode_char(code))
+    }
+
+    /**
+    Copy the characters shown on a row, according to the shadow buffer, into
+    `buf` as UTF-8. The text is truncated if `buf` is too small.
+    Returns `None` if the row is outside the display.
+    */
+    pub fn row_str<'a>(&self, row: u8, buf: &'a mut [u8]) -> Option<&'a str> {
+        if row >= self.rows {
+            return None;
+        }
+        let cells = self.buffer.row(row)?;
+        let cols = (self.cols as usize).min(cells.len());
+
+        let mut len = 0;
+        for code in &cells[..cols] {
+            let c = self.decode_char(*code);
+            if len + c.len_utf8() > buf.len() {
+                break;
+            }
+            len += c.encode_utf8(&mut buf[len..]).len();
+        }
+
+        core::str::from_utf8(&buf[..len]).ok()
+    }
+
+    /**
+    Serialize the whole screen, according to the shadow buffer, into `buf` as
+    UTF-8 text with one line per row. Output is truncated if `buf` is too small.
+    Returns the number of bytes written.
+    */
+    pub fn capture(&self, buf: &mut [u8]) -> usize {
+        let mut len = 0;
+        for row in 0..self.rows {
+            if row > 0 {
+                if len == buf.len() {
+                    break;
+                }
+                buf[len] = b'\n';
+                len += 1;
+            }
+            len += self.row_str(row, &mut buf[len..]).map_or(0, str::len);
+        }
+        len
+    }
+
+    /**
+    32-bit FNV-1a hash of the screen contents, according to the shadow buffer.
+    Two screens showing the same characters hash to the same value, which makes
+    it cheap to detect changes or compare against an expected screen.
+    */
+    pub fn content_hash(&self) -> u32 {
+        self.buffer
+            .hash(self.cols.min(buffer::MAX_COLS as u8), self.rows)
+    }
+
+    /*********** mid level commands, for sending data/cmds */
+
+    /**
+    Adds a string to the current position. The cursor will advance
+    after this call to the next column
+    # Errors
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn print(&mut self, s: &str) -> Result<(), Error<B::Error>> {
+        for c in s.chars() {
+            if c == '\n' {
+                // Stays on the last row
+                let row = self.position.1.saturating_add(1);
+                self.set_cursor_position(0, row)?;
+                continue;
+            }
+
+            // Past the last column, or the first one when right to left
+            let (col, row) = self.position;
+            if self.wrap && (col >= self.cols || self.past_left_edge) {
+                let col = match self.control.entries {
+                    Entries::LEFT => 0,
+                    Entries::RIGHT => self.cols - 1,
+                };
+                self.set_cursor_position(col, (row + 1) % self.rows)?;
+            }
+            self.write(self.encode_char(c))?;
+        }
+
+        Ok(())
+    }
+
+    /**
+    Scroll `text` across `row` from right to left, `passes` times, moving one
+    column every `step_ms` milliseconds. Blocks until done, and moves the
+    cursor back to where it was before the call. See `Marquee` for smooth
+    scrolling that does not block.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn scroll_text(
+        &mut self,
+        row: u8,
+        text: &str,
+        step_ms: u32,
+        passes: u32,
+    ) -> Result<(), Error<B::Error>> {
+        let (prev_col, prev_row) = self.position;
+        let cols = self.cols as usize;
+        let steps = text.chars().count() + cols;
+
+        for _ in 0..passes {
+            for step in 0..=steps {
+                // The text starts off the right edge and ends off the left one
+                self.set_cursor_position(0, row)?;
+                let blanks = cols.saturating_sub(step);
+                let mut chars = text.chars().skip(step.saturating_sub(cols));
+                for col in 0..cols {
+                    let c = if col < blanks { None } else { chars.next() };
+                    self.write(c.map_or(b' ', |c| self.encode_char(c)))?;
+                }
+
+                if self.buffering {
+                    self.flush()?;
+                }
+                self.delay.wait_ms(step_ms);
+            }
+        }
+
+        self.set_cursor_position(prev_col, prev_row)
+    }
+
+    /**
+    Fill the screen with a test pattern. Like `print_screen`, only the cells
+    that change are sent, and when buffering they are only staged.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn show_test_pattern(&mut self, pattern: Pattern) -> Result<(), Error<B::Error>> {
+        for row in 0..self.rows.min(buffer::MAX_ROWS as u8) {
+            for col in 0..self.cols.min(buffer::MAX_COLS as u8) {
+                let address = col.wrapping_add(self.row_offsets[row as usize]) & 0x7f;
+                let code = pattern.code(col, row, self.cols, address);
+                self.buffer.stage(col, row, code);
+            }
+        }
+
+        self.flush_unless_buffering()
+    }
+
+    /**
+    Move the cursor to `col`, `row` and print `s` from there.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::InvalidPosition` if the position is outside the display. Nothing
+    is sent in that case.
+    */
+    pub fn print_at(&mut self, col: u8, row: u8, s: &str) -> Result<(), Error<B::Error>> {
+        self.try_set_cursor_position(col, row)?;
+        self.print(s)
+    }
+
+    /**
+    Print `s` from the start of `row`, cut to the width of the display. When
+    it does not fit, the last column shows the ellipsis set with
+    `set_ellipsis` instead of the rest of the text. The rest of the row is
+    left as it is, and the cursor stays after the text.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::InvalidPosition` if the row is outside the display.
+    */
+    pub fn print_truncated(&mut self, row: u8, s: &str) -> Result<(), Error<B::Error>> {
+        self.try_set_cursor_position(0, row)?;
+
+        let cols = self.cols as usize;
+        let fits = s.chars().nth(cols).is_none();
+        for (col, c) in s.chars().take(cols).enumerate() {
+            let c = if !fits && col == cols - 1 {
+                self.ellipsis
+            } else {
+                c
+            };
+            self.write(self.encode_char(c))?;
+        }
+        Ok(())
+    }
+
+    /**
+    Print `s` on `row` so that it ends on the last column, e.g. for values and
+    units. When it is wider than the display, only its last characters are
+    shown. The rest of the row is left as it is.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::InvalidPosition` if the row is outside the display.
+    */
+    pub fn print_right_aligned(&mut self, row: u8, s: &str) -> Result<(), Error<B::Error>> {
+        if row >= self.rows {
+            return Err(Error::InvalidPosition);
+        }
+
+        let len = s.chars().count();
+        let shown = len.min(self.cols as usize);
+        self.set_cursor_position(self.cols - shown as u8, row)?;
+        for c in s.chars().skip(len - shown) {
+            self.write(self.encode_char(c))?;
+        }
+        Ok(())
+    }
+
+    /**
+    Fill `row` with spaces, without the delay of `clear`, and move the cursor
+    to the start of the row.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::InvalidPosition` if the row is outside the display.
+    */
+    pub fn clear_row(&mut self, row: u8) -> Result<(), Error<B::Error>> {
+        self.clear_region(0, row, self.cols)
+    }
+
+    /**
+    Write `len` spaces from `col`, `row`, stopping at the end of the row, and
+    move the cursor back to `col`, `row`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::InvalidPosition` if the position is outside the display.
+    */
+    pub fn clear_region(&mut self, col: u8, row: u8, len: u8) -> Result<(), Error<B::Error>> {
+        self.try_set_cursor_position(col, row)?;
+        for _ in col..col.saturating_add(len).min(self.cols) {
+            self.write(b' ')?;
+        }
+        self.set_cursor_position(col, row)
+    }
+
+    /**
+    Replace the whole screen with `lines`, one per row. Each line is clipped to
+    the width of the display and padded with spaces, and rows past the last
+    line are blanked. Only the cells that change are sent, in one flush, so the
+    screen does not flicker as it would with `clear` followed by prints. When
+    buffering, the cells are only staged for the next `flush`.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::TooManyLines` if there are more lines than rows.
+    */
+    pub fn print_screen(&mut self, lines: &[&str]) -> Result<(), Error<B::Error>> {
+        if lines.len() > self.rows as usize {
+            return Err(Error::TooManyLines);
+        }
+
+        for row in 0..self.rows {
+            let line = lines.get(row as usize).copied().unwrap_or("");
+            self.stage_row(row, 0, line);
+        }
+
+        self.flush_unless_buffering()
+    }
+
+    /**
+    Replace `row` with `s`, padded with spaces to the width of the display, or
+    cut to it, so nothing is left of the previous text. Only the cells that
+    change are sent, in one pass, so the row does not flicker as it would when
+    cleared first. The cursor does not move.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::InvalidPosition` if the row is outside the display.
+    */
+    pub fn overwrite_line(&mut self, row: u8, s: &str) -> Result<(), Error<B::Error>> {
+        if row >= self.rows {
+            return Err(Error::InvalidPosition);
+        }
+
+        self.stage_row(row, 0, s);
+        self.flush_unless_buffering()
+    }
+
+    /**
+    Replace `row` with `s`, centered and padded with spaces, cut to the width
+    of the display if too long. When the padding cannot be split evenly, the
+    extra space goes to the right. Like `print_screen`, only the cells that
+    change are sent, and the cursor does not move.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any, or
+    `Error::InvalidPosition` if the row is outside the display.
+    */
+    pub fn print_centered(&mut self, row: u8, s: &str) -> Result<(), Error<B::Error>> {
+        if row >= self.rows {
+            return Err(Error::InvalidPosition);
+        }
+
+        let len = s.chars().count().min(self.cols as usize) as u8;
+        self.stage_row(row, (self.cols - len) / 2, s);
+        self.flush_unless_buffering()
+    }
+
+    // Stage `s` on `row` from `col`, cut to the width of the display, with
+    // spaces before and after it
+    fn stage_row(&mut self, row: u8, col: u8, s: &str) {
+        let mut chars = s.chars();
+        for x in 0..self.cols {
+            let code = if x < col {
+                b' '
+            } else {
+                chars.next().map_or(b' ', |c| self.charset.encode(c))
+            };
+            self.buffer.stage(x, row, code);
+        }
+    }
+
+    // Send what was staged right away, unless the user is buffering
+    fn flush_unless_buffering(&mut self) -> Result<(), Error<B::Error>> {
+        if self.buffering {
+            return Ok(());
+        }
+        self.flush()
+    }
+
+    // Send two bytes to the display
+    pub fn write(&mut self, value: u8) -> Result<(), Error<B::Error>> {
+        // Past the left edge nothing is shown, so nothing is sent
+        if self.past_left_edge {
+            return Ok(());
+        }
+
+        let (col, row) = self.position;
+        if self.buffering {
+            self.buffer.stage(col, row, value);
+        } else {
+            self.send(value, Register::Data)?;
+            self.buffer.store(col, row, value);
+        }
+        match self.next_col(col) {
+            Some(col) => self.position.0 = col,
+            None => self.past_left_edge = true,
+        }
+        Ok(())
+    }
+
+    /**
+    Write ROM codes as they are, without mapping chars as `print` does, e.g.
+    for text encoded ahead of time. Newlines are written as codes too.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), Error<B::Error>> {
+        self.write_iter(bytes.iter().copied())
+    }
+
+    /**
+    Write ROM codes as they are produced, like `write_bytes`, so data decoded
+    or generated on the fly does not need a buffer. Stops at the first error.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn write_iter<I: IntoIterator<Item = u8>>(
+        &mut self,
+        bytes: I,
+    ) -> Result<(), Error<B::Error>> {
+        for byte in bytes {
+            self.write(byte)?;
+        }
+        Ok(())
+    }
+
+    // Column the address counter moves to after a write at `col`, or `None`
+    // past the left edge, when writing right to left from the first column
+    fn next_col(&self, col: u8) -> Option<u8> {
+        match self.control.entries {
+            Entries::LEFT => Some(col.wrapping_add(1)),
+            Entries::RIGHT => col.checked_sub(1),
+        }
+    }
+
+    fn upload_char(&mut self, location: u8, charmap: [u8; 8]) -> Result<(), Error<B::Error>> {
+        let location = location & 0x7;
+        self.command(Mode::SETCGRAMADDR as u8 | (location << 3))?;
+
+        for item in &charmap {
+            self.send(*item, Register::Data)?;
+        }
+        self.glyphs[location as usize] = Some(charmap);
+        self.charset.custom[location as usize] = None;
+
+        // Point the address counter back at DDRAM so the next write lands on screen
+        let (col, row) = self.position;
+        self.set_ddram_address(col, row)
+    }
+
+    // Map a char to the code of the glyph in the display's character ROM
+    fn encode_char(&self, c: char) -> u8 {
+        self.charset.encode(c)
+    }
+
+    // Map the code of a glyph in the display's character ROM back to a char
+    fn decode_char(&self, code: u8) -> char {
+        self.charset.decode(code)
+    }
+
+    // Set one of the display's control options and then send the updated set of options to the display
+    fn write_display_control(&mut self) -> Result<(), Error<B::Error>> {
+        self.command(Mode::DISPLAYCONTROL as u8 | self.control.value())
+    }
+
+    // Send a command to every controller
+    fn command(&mut self, value: u8) -> Result<(), Error<B::Error>> {
+        let selected = self.selected;
+        self.select(Controllers::Both);
+        let sent = self.send(value, Register::Command);
+        self.select(selected);
+        sent
+    }
+
+    // Shift the whole display by one column, without touching DDRAM
+    fn shift_display(&mut self, direction: Direction) -> Result<(), Error<B::Error>> {
+        self.command(Mode::CURSORSHIFT as u8 | MoveSelect::DISPLAY as u8 | direction as u8)
+    }
+
+    // Move the cursor by one column, without touching DDRAM
+    fn shift_cursor(&mut self, direction: Direction) -> Result<(), Error<B::Error>> {
+        self.command(Mode::CURSORSHIFT as u8 | MoveSelect::CURSOR as u8 | direction as u8)
+    }
+
+    // Point the address counter at `col`, `row`, selecting the controller
+    // showing the row for the next writes
+    fn set_ddram_address(&mut self, col: u8, row: u8) -> Result<(), Error<B::Error>> {
+        let (controller, line) = if self.bus.controllers() > 1 && row >= 2 {
+            (Controllers::Second, row - 2)
+        } else {
+            (Controllers::First, row)
+        };
+        self.select(controller);
+        let address = col.wrapping_add(self.row_offsets[line as usize]) & 0x7f;
+        self.send(Mode::SETDDRAMADDR as u8 | address, Register::Command)
+    }
+}
+
+/// Formatted output with `write!`, printed as by `print`. I2C errors are
+/// reported as `fmt::Error`.
+impl<B: LcdBus, D: WaitStrategy> fmt::Write for Lcd<B, D> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.print(s).map_err(|_| fmt::Error)
+    }
+}
+
+/// Formatted output with `uwrite!`, printed as by `print`
+#[cfg(feature = "ufmt")]
+impl<B: LcdBus, D: WaitStrategy> ufmt_write::uWrite for Lcd<B, D> {
+    type Error = Error<B::Error>;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.print(s)
+    }
+}
+
+impl<B, D, S> Drop for Lcd<B, D, S> {
+    fn drop(&mut self) {
+        if let Some(on_drop) = self.on_drop {
+            on_drop(self);
+        }
+    }
+}
+
+impl<B: LcdBus, D: WaitStrategy, S> Lcd<B, D, S> {
+    fn into_state<T>(self) -> Lcd<B, D, T> {
+        // `Lcd` implements `Drop`, so the fields are moved out of a
+        // `ManuallyDrop` instead of `self`
+        let lcd = ManuallyDrop::new(self);
+
+        // SAFETY: each field that is not `Copy` is read exactly once, and
+        // `lcd` is never dropped, so none of them is dropped twice
+        let (bus, control, delay, buffer) = unsafe {
+            (
+                ptr::read(&lcd.bus),
+                ptr::read(&lcd.control),
+                ptr::read(&lcd.delay),
+                ptr::read(&lcd.buffer),
+            )
+        };
+
+        Lcd {
+            bus,
+            control,
+            sleeping: lcd.sleeping,
+            delay,
+            cols: lcd.cols,
+            rows: lcd.rows,
+            row_offsets: lcd.row_offsets,
+            position: lcd.position,
+            past_left_edge: lcd.past_left_edge,
+            buffer,
+            buffering: lcd.buffering,
+            wrap: lcd.wrap,
+            ellipsis: lcd.ellipsis,
+            clear_on_init: lcd.clear_on_init,
+            selected: lcd.selected,
+            font: lcd.font,
+            lines: lcd.lines,
+            drop_policy: lcd.drop_policy,
+            // Only known once the state is
+            on_drop: None,
+            glyphs: lcd.glyphs,
+            heartbeat: lcd.heartbeat,
+            heartbeat_phase: lcd.heartbeat_phase,
+            charset: lcd.charset,
+            contrast: lcd.contrast,
+            clock: lcd.clock,
+            ready_at: lcd.ready_at,
+            frame_stats: lcd.frame_stats,
+            #[cfg(feature = "latency-histogram")]
+            latency: lcd.latency,
+            state: PhantomData,
+        }
+    }
+
+    /**
+    Give back the bus and the delay, so they can be used for other devices.
+    The display is left as it is, the drop policy is not applied: turn the
+    display off first with `set_display` if needed. Behind a backpack,
+    `release` gives back the I2C bus itself.
+    */
+    pub fn into_parts(self) -> (B, D) {
+        let lcd = ManuallyDrop::new(self);
+
+        // SAFETY: the bus and the delay are read once, and `lcd` is never
+        // dropped. The other fields have no drop glue.
+        unsafe { (ptr::read(&lcd.bus), ptr::read(&lcd.delay)) }
+    }
+
+    /************ low level data pushing commands **********/
+
+    fn send(&mut self, data: u8, register: Register) -> Result<(), Error<B::Error>> {
+        #[cfg(feature = "latency-histogram")]
+        let started = self.clock.map(|clock| clock());
+
+        self.bus
+            .write_byte(data, register, &mut self.delay)
+            .map_err(Error::Bus)?;
+
+        #[cfg(feature = "latency-histogram")]
+        if let Some((clock, started)) = self.clock.zip(started) {
+            self.latency.record(clock().wrapping_sub(started));
+        }
+
+        if self.delay.polls_busy_flag() {
+            self.wait_while_busy()?;
+        }
+        Ok(())
+    }
+
+    // Clearing and returning home take up to 1.52ms, other commands 37µs at
+    // most, which is less than the time taken to send the next one over I2C
+    fn wait_for_long_command(&mut self) {
+        if !self.delay.polls_busy_flag() {
+            self.delay.wait_ms(2);
+        }
+    }
+
+    // Read the busy flag until the controller is done, giving up after a
+    // while in case R/W is not wired
+    fn wait_while_busy(&mut self) -> Result<(), Error<B::Error>> {
+        const MAX_POLLS: u8 = 100;
+
+        // Controllers are read one at a time, so they do not both drive the lines
+        let selected = self.selected;
+        if selected != Controllers::Both || self.bus.controllers() < 2 {
+            return self.poll_busy_flag(MAX_POLLS);
+        }
+        for controller in [Controllers::First, Controllers::Second] {
+            self.select(controller);
+            let polled = self.poll_busy_flag(MAX_POLLS);
+            self.select(selected);
+            polled?;
+        }
+        Ok(())
+    }
+
+    fn poll_busy_flag(&mut self, max_polls: u8) -> Result<(), Error<B::Error>> {
+        for _ in 0..max_polls {
+            if self.read_byte(Register::Command)? & 0x80 == 0 {
+                return Ok(());
+            }
+        }
+        Err(Error::Busy)
+    }
+
+    // Read a byte from the controller, the busy flag and the address counter
+    // with `Register::Command`, the data at the address counter otherwise
+    fn read_byte(&mut self, register: Register) -> Result<u8, Error<B::Error>> {
+        self.bus
+            .read_byte(register, &mut self.delay)
+            .map_err(Error::Bus)?
+            .ok_or(Error::Unsupported)
+    }
+
+    // Send the high nibble of `value` alone, as a command
+    fn write_nibble(&mut self, value: u8) -> Result<(), Error<B::Error>> {
+        self.bus
+            .write_nibble(value & 0xf0, Register::Command, &mut self.delay)
+            .map_err(Error::Bus)
+    }
+
+    // Send the next transfers to `controllers`
+    fn select(&mut self, controllers: Controllers) {
+        self.selected = controllers;
+        self.bus.select(controllers);
     }
 }
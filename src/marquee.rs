@@ -0,0 +1,82 @@
+//! Smooth scrolling of long text with the display shift of the controller.
+
+use crate::{Direction, Error, Lcd, LcdBus, Mode, Register, WaitStrategy, buffer::MAX_COLS};
+
+/// Scrolls a text of up to 40 characters through a row by shifting the
+/// display, instead of rewriting the row on every step.
+///
+/// The HD44780 keeps 40 characters per line whatever the width of the
+/// display, so the whole text is written once, the part that does not fit
+/// going to the columns past the right edge, and every `step` is a single
+/// shift command.
+///
+/// The controller shifts every line at once: other rows scroll along, so this
+/// suits displays where the other rows are blank or hold the same marquee. On
+/// four-row displays, the third and fourth rows are the off-screen part of the
+/// first and second ones, so they cannot be used with a marquee either.
+#[derive(Copy, Clone, Debug)]
+pub struct Marquee {
+    row: u8,
+    offset: u8,
+}
+
+impl Marquee {
+    /// A marquee on `row`
+    pub const fn new(row: u8) -> Self {
+        Marquee { row, offset: 0 }
+    }
+
+    /// How many columns the display is shifted to the left by
+    pub fn offset(&self) -> u8 {
+        self.offset
+    }
+
+    /**
+    Write `text` to the whole line of DDRAM behind the row, padded with spaces
+    and truncated to 40 characters, and undo any shift so it starts at the
+    first column. The text is sent right away, even when buffering, and the
+    cursor is moved back to where it was before the call.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn load<B: LcdBus, D: WaitStrategy>(
+        &mut self,
+        lcd: &mut Lcd<B, D>,
+        text: &str,
+    ) -> Result<(), Error<B::Error>> {
+        let row = self.row.min(lcd.rows - 1);
+
+        lcd.command(Mode::RETURNHOME as u8)?;
+        lcd.wait_for_long_command();
+        self.offset = 0;
+
+        lcd.set_ddram_address(0, row)?;
+        let mut chars = text.chars();
+        for col in 0..MAX_COLS as u8 {
+            let code = chars.next().map_or(b' ', |c| lcd.encode_char(c));
+            lcd.send(code, Register::Data)?;
+            lcd.buffer.store(col, row, code);
+        }
+
+        let (col, row) = lcd.position;
+        lcd.set_ddram_address(col, row)
+    }
+
+    /**
+    Move the text one column to the left, wrapping around after 40 steps.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn step<B: LcdBus, D: WaitStrategy>(
+        &mut self,
+        lcd: &mut Lcd<B, D>,
+    ) -> Result<(), Error<B::Error>> {
+        lcd.shift_display(Direction::LEFT)?;
+        self.offset = (self.offset + 1) % MAX_COLS as u8;
+        Ok(())
+    }
+}
@@ -0,0 +1,129 @@
+//! Transport through the MCP23008 expander of Adafruit I2C backpacks.
+
+use embedded_hal::i2c::I2c;
+
+use crate::{Address, Backlight, LcdBus, Register, WaitStrategy};
+
+/// I/O direction register, a set bit making the pin an input
+const IODIR: u8 = 0x00;
+/// Port register, writing it sets the output latches
+const GPIO: u8 = 0x09;
+
+const RS: u8 = 0x02;
+const ENABLE: u8 = 0x04;
+const BACKLIGHT: u8 = 0x80;
+
+/// The MCP23008 expander of the Adafruit I2C/SPI backpack, driving the
+/// display in 4-bit mode with RS on GP1, E on GP2, D4–D7 on GP3–GP6 and the
+/// backlight on GP7. R/W is tied low, so the busy flag cannot be read.
+///
+/// Unlike the PCF8574, the pins start as inputs and each write selects a
+/// register: the pins are made outputs on the first write.
+pub struct Mcp23008<I2C> {
+    i2c: I2C,
+    address: u8,
+    backlight: Backlight,
+    /// Whether the pins were made outputs
+    configured: bool,
+    /// Bytes written, wrapping
+    bytes: u32,
+}
+
+impl<I2C: I2c> Mcp23008<I2C> {
+    pub fn new(i2c: I2C, address: Address) -> Self {
+        Mcp23008 {
+            i2c,
+            address: address.value(),
+            backlight: Backlight::On,
+            configured: false,
+            bytes: 0,
+        }
+    }
+
+    /// I2C address of the backpack
+    pub fn address(&self) -> Address {
+        Address::unchecked(self.address)
+    }
+
+    /// Give back the I2C bus
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+
+    // Put the high nibble of `value` on D4–D7 and clock it in
+    fn write4bits(
+        &mut self,
+        value: u8,
+        register: Register,
+        wait: &mut impl WaitStrategy,
+    ) -> Result<(), I2C::Error> {
+        let mut pins = (value & 0xf0) >> 1;
+        if register == Register::Data {
+            pins |= RS;
+        }
+
+        self.write_pins(pins)?;
+        self.write_pins(pins | ENABLE)?; // En high
+        wait.wait_us(1);
+        self.write_pins(pins)?; // En low
+        wait.wait_us(1);
+
+        Ok(())
+    }
+
+    fn write_pins(&mut self, pins: u8) -> Result<(), I2C::Error> {
+        if !self.configured {
+            self.write_register(IODIR, 0x00)?;
+            self.configured = true;
+        }
+
+        let backlight = match self.backlight {
+            Backlight::On => BACKLIGHT,
+            Backlight::Off => 0,
+        };
+        self.write_register(GPIO, pins | backlight)
+    }
+
+    fn write_register(&mut self, register: u8, value: u8) -> Result<(), I2C::Error> {
+        self.i2c.write(self.address, &[register, value])?;
+        self.bytes = self.bytes.wrapping_add(2);
+        Ok(())
+    }
+}
+
+impl<I2C: I2c> LcdBus for Mcp23008<I2C> {
+    type Error = I2C::Error;
+
+    fn write_nibble(
+        &mut self,
+        value: u8,
+        register: Register,
+        wait: &mut impl WaitStrategy,
+    ) -> Result<(), Self::Error> {
+        self.write4bits(value, register, wait)
+    }
+
+    fn write_byte(
+        &mut self,
+        value: u8,
+        register: Register,
+        wait: &mut impl WaitStrategy,
+    ) -> Result<(), Self::Error> {
+        self.write4bits(value, register, wait)?;
+        self.write4bits(value << 4, register, wait)
+    }
+
+    fn set_backlight(&mut self, backlight: Backlight) -> Result<(), Self::Error> {
+        self.backlight = backlight;
+        self.write_pins(0)
+    }
+
+    fn command_bytes(&self) -> u32 {
+        // Three register writes for each nibble
+        12
+    }
+
+    fn bytes_written(&self) -> u32 {
+        self.bytes
+    }
+}
@@ -0,0 +1,239 @@
+//! Transport through the MCP23017 expander of Adafruit RGB LCD shields, which
+//! also reads their buttons.
+
+use embedded_hal::i2c::I2c;
+
+use crate::{Address, Backlight, LcdBus, Register, WaitStrategy};
+
+/// I/O direction registers, a set bit making the pin an input
+const IODIRA: u8 = 0x00;
+const IODIRB: u8 = 0x01;
+/// Pull-up registers
+const GPPUA: u8 = 0x0C;
+/// Port registers, writing them sets the output latches
+const GPIOA: u8 = 0x12;
+const GPIOB: u8 = 0x13;
+
+/// Port A: the buttons, active low, and the red and green LEDs, active low
+const BUTTONS: u8 = 0x1F;
+const RED: u8 = 0x40;
+const GREEN: u8 = 0x80;
+/// Port B: the blue LED, active low, D7–D4 in reverse order, E, R/W and RS
+const BLUE: u8 = 0x01;
+const DATA: u8 = 0x1E;
+const ENABLE: u8 = 0x20;
+const READ: u8 = 0x40;
+const RS: u8 = 0x80;
+
+/// Buttons of the shield held down, as read by `read_buttons`
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Buttons {
+    pub select: bool,
+    pub right: bool,
+    pub down: bool,
+    pub up: bool,
+    pub left: bool,
+}
+
+impl Buttons {
+    fn from_port(port: u8) -> Self {
+        // Pulled up, so a pressed button reads low
+        let pressed = !port;
+        Buttons {
+            select: pressed & 0x01 != 0,
+            right: pressed & 0x02 != 0,
+            down: pressed & 0x04 != 0,
+            up: pressed & 0x08 != 0,
+            left: pressed & 0x10 != 0,
+        }
+    }
+
+    /// Whether no button is held down
+    pub fn is_empty(&self) -> bool {
+        *self == Buttons::default()
+    }
+}
+
+/// The MCP23017 expander of the Adafruit RGB LCD shield, driving the display
+/// in 4-bit mode on port B, with RS on GPB7, R/W on GPB6, E on GPB5 and D4–D7
+/// on GPB4–GPB1, while port A reads the five buttons on GPA0–GPA4.
+///
+/// The red, green and blue LEDs of the backlight, on GPA6, GPA7 and GPB0, are
+/// switched together by `set_backlight`. The pins are configured on the first
+/// write.
+pub struct Mcp23017<I2C> {
+    i2c: I2C,
+    address: u8,
+    backlight: Backlight,
+    /// Whether the pins were configured
+    configured: bool,
+    /// Bytes written, wrapping
+    bytes: u32,
+}
+
+impl<I2C: I2c> Mcp23017<I2C> {
+    pub fn new(i2c: I2C, address: Address) -> Self {
+        Mcp23017 {
+            i2c,
+            address: address.value(),
+            backlight: Backlight::On,
+            configured: false,
+            bytes: 0,
+        }
+    }
+
+    /// I2C address of the shield
+    pub fn address(&self) -> Address {
+        Address::unchecked(self.address)
+    }
+
+    /// Give back the I2C bus
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+
+    /**
+    Read which buttons are held down.
+
+    # Errors
+
+    Returns a `Result` that will report I2C errors, if any.
+    */
+    pub fn read_buttons(&mut self) -> Result<Buttons, I2C::Error> {
+        self.configure()?;
+        let mut port = [0];
+        self.i2c.write_read(self.address, &[GPIOA], &mut port)?;
+        self.bytes = self.bytes.wrapping_add(1);
+        Ok(Buttons::from_port(port[0]))
+    }
+
+    // Put the high nibble of `value` on D4–D7 and clock it in
+    fn write4bits(
+        &mut self,
+        value: u8,
+        register: Register,
+        wait: &mut impl WaitStrategy,
+    ) -> Result<(), I2C::Error> {
+        let pins = data_pins(value) | register_pin(register);
+
+        self.write_port_b(pins)?;
+        self.write_port_b(pins | ENABLE)?; // En high
+        wait.wait_us(1);
+        self.write_port_b(pins)?; // En low
+        wait.wait_us(1);
+
+        Ok(())
+    }
+
+    fn write_port_b(&mut self, pins: u8) -> Result<(), I2C::Error> {
+        self.configure()?;
+        let blue = match self.backlight {
+            Backlight::On => 0,
+            Backlight::Off => BLUE,
+        };
+        self.write_register(GPIOB, pins | blue)
+    }
+
+    // Make the buttons pulled up inputs and the rest outputs, once
+    fn configure(&mut self) -> Result<(), I2C::Error> {
+        if !self.configured {
+            self.write_register(IODIRA, BUTTONS)?;
+            self.write_register(GPPUA, BUTTONS)?;
+            self.write_register(IODIRB, 0x00)?;
+            self.configured = true;
+        }
+        Ok(())
+    }
+
+    fn write_register(&mut self, register: u8, value: u8) -> Result<(), I2C::Error> {
+        self.i2c.write(self.address, &[register, value])?;
+        self.bytes = self.bytes.wrapping_add(2);
+        Ok(())
+    }
+}
+
+// Pins of port B holding the high nibble of `value`
+fn data_pins(value: u8) -> u8 {
+    (value & 0x10) | (value & 0x20) >> 2 | (value & 0x40) >> 4 | (value & 0x80) >> 6
+}
+
+// High nibble held by the pins of port B
+fn data_value(pins: u8) -> u8 {
+    (pins & 0x10) | (pins & 0x08) << 2 | (pins & 0x04) << 4 | (pins & 0x02) << 6
+}
+
+fn register_pin(register: Register) -> u8 {
+    match register {
+        Register::Command => 0,
+        Register::Data => RS,
+    }
+}
+
+impl<I2C: I2c> LcdBus for Mcp23017<I2C> {
+    type Error = I2C::Error;
+
+    fn write_nibble(
+        &mut self,
+        value: u8,
+        register: Register,
+        wait: &mut impl WaitStrategy,
+    ) -> Result<(), Self::Error> {
+        self.write4bits(value, register, wait)
+    }
+
+    fn write_byte(
+        &mut self,
+        value: u8,
+        register: Register,
+        wait: &mut impl WaitStrategy,
+    ) -> Result<(), Self::Error> {
+        self.write4bits(value, register, wait)?;
+        self.write4bits(value << 4, register, wait)
+    }
+
+    fn set_backlight(&mut self, backlight: Backlight) -> Result<(), Self::Error> {
+        self.backlight = backlight;
+        self.configure()?;
+        let red_green = match backlight {
+            Backlight::On => 0,
+            Backlight::Off => RED | GREEN,
+        };
+        self.write_register(GPIOA, red_green)?;
+        self.write_port_b(0)
+    }
+
+    fn read_byte(
+        &mut self,
+        register: Register,
+        wait: &mut impl WaitStrategy,
+    ) -> Result<Option<u8>, Self::Error> {
+        // The display drives the data pins while they are inputs
+        self.configure()?;
+        self.write_register(IODIRB, DATA)?;
+        let read = READ | register_pin(register);
+
+        let mut value = 0;
+        // High nibble first
+        for shift in [0, 4] {
+            self.write_port_b(read | ENABLE)?;
+            wait.wait_us(1);
+            let mut pins = [0];
+            self.i2c.write_read(self.address, &[GPIOB], &mut pins)?;
+            self.bytes = self.bytes.wrapping_add(1);
+            self.write_port_b(read)?;
+            value |= data_value(pins[0]) >> shift;
+        }
+
+        self.write_register(IODIRB, 0x00)?;
+        Ok(Some(value))
+    }
+
+    fn command_bytes(&self) -> u32 {
+        // Three register writes for each nibble
+        12
+    }
+
+    fn bytes_written(&self) -> u32 {
+        self.bytes
+    }
+}
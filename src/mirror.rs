@@ -0,0 +1,114 @@
+//! Duplicate the output of a display on a second one.
+
+use core::fmt;
+
+use crate::{Backlight, Blink, CharacterDisplay, Cursor, Display};
+
+/// Forwards every operation to a primary display, then to a secondary one,
+/// such as a simulator or a serial mirror used to watch the panel remotely.
+pub struct Mirror<P, S> {
+    primary: P,
+    secondary: S,
+}
+
+/// Error of the display an operation failed on
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MirrorError<P, S> {
+    Primary(P),
+    Secondary(S),
+}
+
+impl<P: CharacterDisplay, S: CharacterDisplay> Mirror<P, S> {
+    pub fn new(primary: P, secondary: S) -> Self {
+        Mirror { primary, secondary }
+    }
+
+    pub fn primary(&mut self) -> &mut P {
+        &mut self.primary
+    }
+
+    pub fn secondary(&mut self) -> &mut S {
+        &mut self.secondary
+    }
+
+    /// Give back both displays
+    pub fn release(self) -> (P, S) {
+        (self.primary, self.secondary)
+    }
+
+    // Run an operation on both displays, even if it fails on the primary one
+    fn both(
+        &mut self,
+        mut operation_primary: impl FnMut(&mut P) -> Result<(), P::Error>,
+        mut operation_secondary: impl FnMut(&mut S) -> Result<(), S::Error>,
+    ) -> Result<(), MirrorError<P::Error, S::Error>> {
+        let primary = operation_primary(&mut self.primary);
+        let secondary = operation_secondary(&mut self.secondary);
+        primary.map_err(MirrorError::Primary)?;
+        secondary.map_err(MirrorError::Secondary)
+    }
+}
+
+impl<P: CharacterDisplay, S: CharacterDisplay> CharacterDisplay for Mirror<P, S> {
+    type Error = MirrorError<P::Error, S::Error>;
+
+    fn clear(&mut self) -> Result<(), Self::Error> {
+        self.both(|d| d.clear(), |d| d.clear())
+    }
+
+    fn home(&mut self) -> Result<(), Self::Error> {
+        self.both(|d| d.home(), |d| d.home())
+    }
+
+    fn set_cursor_position(&mut self, col: u8, row: u8) -> Result<(), Self::Error> {
+        self.both(
+            |d| d.set_cursor_position(col, row),
+            |d| d.set_cursor_position(col, row),
+        )
+    }
+
+    fn print(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.both(|d| d.print(s), |d| d.print(s))
+    }
+
+    fn write(&mut self, value: u8) -> Result<(), Self::Error> {
+        self.both(|d| d.write(value), |d| d.write(value))
+    }
+
+    fn create_char(&mut self, location: u8, charmap: [u8; 8]) -> Result<(), Self::Error> {
+        self.both(
+            |d| d.create_char(location, charmap),
+            |d| d.create_char(location, charmap),
+        )
+    }
+
+    fn set_display(&mut self, display: Display) -> Result<(), Self::Error> {
+        self.both(|d| d.set_display(display), |d| d.set_display(display))
+    }
+
+    fn set_cursor(&mut self, cursor: Cursor) -> Result<(), Self::Error> {
+        self.both(|d| d.set_cursor(cursor), |d| d.set_cursor(cursor))
+    }
+
+    fn set_blink(&mut self, blink: Blink) -> Result<(), Self::Error> {
+        self.both(|d| d.set_blink(blink), |d| d.set_blink(blink))
+    }
+
+    fn set_backlight(&mut self, backlight: Backlight) -> Result<(), Self::Error> {
+        self.both(
+            |d| d.set_backlight(backlight),
+            |d| d.set_backlight(backlight),
+        )
+    }
+}
+
+impl<P: fmt::Debug, S: fmt::Debug> fmt::Display for MirrorError<P, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MirrorError::Primary(error) => write!(f, "primary display error: {error:?}"),
+            MirrorError::Secondary(error) => write!(f, "secondary display error: {error:?}"),
+        }
+    }
+}
+
+impl<P: fmt::Debug, S: fmt::Debug> core::error::Error for MirrorError<P, S> {}
@@ -0,0 +1,105 @@
+//! Transport to controllers taking HD44780 commands straight over I2C.
+
+use embedded_hal::i2c::I2c;
+
+use crate::{Address, Backlight, BitMode, LcdBus, Register, WaitStrategy};
+
+/// A controller taking HD44780 commands and characters over I2C, each byte
+/// after a control byte telling which register it goes to, in 8-bit mode, such
+/// as the RW1063 of Midas MCCOG modules. There is no enable line to pulse, nor
+/// nibbles to split.
+pub struct NativeI2c<I2C> {
+    pub(crate) i2c: I2C,
+    address: u8,
+    /// Control bytes sent before commands and characters
+    command: u8,
+    data: u8,
+    /// Bytes written, wrapping
+    bytes: u32,
+}
+
+impl<I2C: I2c> NativeI2c<I2C> {
+    /// Controller with the usual control bytes: 0x00 before commands and 0x40
+    /// before characters
+    pub fn new(i2c: I2C, address: Address) -> Self {
+        Self::with_control_bytes(i2c, address, 0x00, 0x40)
+    }
+
+    /// Controller with other control bytes, e.g. 0x80 before commands on
+    /// controllers reading the Co bit
+    pub fn with_control_bytes(i2c: I2C, address: Address, command: u8, data: u8) -> Self {
+        NativeI2c {
+            i2c,
+            address: address.value(),
+            command,
+            data,
+            bytes: 0,
+        }
+    }
+
+    /// I2C address of the controller
+    pub fn address(&self) -> Address {
+        Address::unchecked(self.address)
+    }
+
+    /// Give back the I2C bus
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+
+    pub(crate) fn command(&mut self, value: u8) -> Result<(), I2C::Error> {
+        self.write(self.command, value)
+    }
+
+    pub(crate) fn data(&mut self, value: u8) -> Result<(), I2C::Error> {
+        self.write(self.data, value)
+    }
+
+    fn write(&mut self, control: u8, value: u8) -> Result<(), I2C::Error> {
+        self.i2c.write(self.address, &[control, value])?;
+        self.bytes = self.bytes.wrapping_add(2);
+        Ok(())
+    }
+}
+
+impl<I2C: I2c> LcdBus for NativeI2c<I2C> {
+    type Error = I2C::Error;
+
+    fn write_nibble(
+        &mut self,
+        value: u8,
+        register: Register,
+        wait: &mut impl WaitStrategy,
+    ) -> Result<(), Self::Error> {
+        self.write_byte(value & 0xf0, register, wait)
+    }
+
+    fn write_byte(
+        &mut self,
+        value: u8,
+        register: Register,
+        _wait: &mut impl WaitStrategy,
+    ) -> Result<(), Self::Error> {
+        match register {
+            Register::Command => self.command(value),
+            Register::Data => self.data(value),
+        }
+    }
+
+    fn set_backlight(&mut self, _backlight: Backlight) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn bit_mode(&self) -> BitMode {
+        BitMode::Bit8
+    }
+
+    fn command_bytes(&self) -> u32 {
+        // A control byte, then the byte
+        2
+    }
+
+    fn bytes_written(&self) -> u32 {
+        self.bytes
+    }
+}
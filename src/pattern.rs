@@ -0,0 +1,45 @@
+//! Screens for checking a display during bring-up.
+
+/// Full block of the character ROM
+const FULL_BLOCK: u8 = 0xff;
+
+/// A screen filling every cell, shown by `Lcd::show_test_pattern`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Pattern {
+    /// Every cell is a full block, to spot dead pixels and weak contrast
+    AllCells,
+    /// Full blocks and blank cells alternate, to spot shorts between data lines
+    Checkerboard,
+    /// `A`–`Z` then `0`–`9`, carrying on from one cell to the next across rows
+    Alphabet,
+    /// The last hex digit of the DDRAM address of each cell, except the first
+    /// two cells of each row which show the address of the row, to debug row
+    /// offsets
+    AddressMap,
+}
+
+impl Pattern {
+    /// Code shown at `col`, `row`, whose DDRAM address is `address`
+    pub(crate) fn code(self, col: u8, row: u8, cols: u8, address: u8) -> u8 {
+        const HEX: &[u8; 16] = b"0123456789ABCDEF";
+        const ALPHABET: &[u8; 36] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+        match self {
+            Pattern::AllCells => FULL_BLOCK,
+            Pattern::Checkerboard if (col + row).is_multiple_of(2) => FULL_BLOCK,
+            Pattern::Checkerboard => b' ',
+            Pattern::Alphabet => {
+                let index = row as usize * cols as usize + col as usize;
+                ALPHABET[index % ALPHABET.len()]
+            }
+            Pattern::AddressMap => {
+                let row_address = address.wrapping_sub(col) & 0x7f;
+                match col {
+                    0 => HEX[(row_address >> 4) as usize],
+                    1 => HEX[(row_address & 0xf) as usize],
+                    _ => HEX[(address & 0xf) as usize],
+                }
+            }
+        }
+    }
+}
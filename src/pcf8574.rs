@@ -0,0 +1,187 @@
+//! Transport through the PCF8574 expander of I2C backpacks.
+
+use embedded_hal::i2c::I2c;
+
+use crate::{
+    Address, Backlight, Controllers, LcdBus, PinMapping, Polarity, Register, WaitStrategy,
+};
+
+/// The PCF8574 or PCF8574A expander of the common I2C backpacks, driving the
+/// display in 4-bit mode, by default with RS on P0, R/W on P1, E on P2, the
+/// backlight on P3 and D4–D7 on P4–P7, see `PinMapping`.
+pub struct Pcf8574<I2C> {
+    i2c: I2C,
+    address: u8,
+    backlight: Backlight,
+    pub(crate) mapping: PinMapping,
+    /// Whether the backlight bit of the expander drives the backlight
+    pub(crate) backlight_pin: bool,
+    /// Expander bit wired to the enable line of the second controller of
+    /// 40x4 displays, 0 if there is none
+    pub(crate) second_enable: u8,
+    /// Enable bits pulsed by the next transfer, selecting the controllers
+    enables: u8,
+    /// State of the expander lines as last written, unknown at first
+    last: Option<u8>,
+    /// Bytes written, wrapping
+    bytes: u32,
+}
+
+impl<I2C: I2c> Pcf8574<I2C> {
+    pub fn new(i2c: I2C, address: Address) -> Self {
+        Pcf8574 {
+            i2c,
+            address: address.value(),
+            backlight: Backlight::On,
+            mapping: PinMapping::PCF8574,
+            backlight_pin: true,
+            second_enable: 0,
+            enables: PinMapping::PCF8574.en_bit(),
+            last: None,
+            bytes: 0,
+        }
+    }
+
+    /// I2C address of the backpack
+    pub fn address(&self) -> Address {
+        Address::unchecked(self.address)
+    }
+
+    /// Give back the I2C bus
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+
+    // Expander bytes setting `pins` up, then clocking them with the enable
+    // pin. A byte takes 90µs on a 100kHz bus, far more than the 450ns the
+    // enable pulse has to last, so the whole pulse goes in one write.
+    fn nibble(&self, pins: u8) -> [u8; 3] {
+        let pins = pins | self.backlight_bit();
+        [pins, pins | self.enables, pins & !self.enables]
+    }
+
+    fn expander_write(&mut self, pins: u8) -> Result<(), I2C::Error> {
+        self.write(&[pins | self.backlight_bit()])
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> Result<(), I2C::Error> {
+        self.i2c.write(self.address, bytes)?;
+        self.bytes = self.bytes.wrapping_add(bytes.len() as u32);
+        self.last = bytes.last().copied();
+        Ok(())
+    }
+
+    // The last state of the lines, with the backlight bit as now set
+    fn with_backlight(&self) -> u8 {
+        let lines = self.last.unwrap_or(0) & !self.mapping.backlight_bit();
+        lines | self.backlight_bit()
+    }
+
+    fn backlight_bit(&self) -> u8 {
+        let bit = self.mapping.backlight_bit();
+        if self.backlight_pin && self.second_enable & bit == 0 {
+            match (self.backlight, self.mapping.backlight_polarity()) {
+                (Backlight::On, Polarity::ActiveHigh) | (Backlight::Off, Polarity::ActiveLow) => {
+                    bit
+                }
+                (Backlight::Off, Polarity::ActiveHigh) | (Backlight::On, Polarity::ActiveLow) => 0,
+            }
+        } else {
+            // Released, as after power up, unless it is the second enable
+            bit & !self.second_enable
+        }
+    }
+
+    fn register_bit(&self, register: Register) -> u8 {
+        match register {
+            Register::Command => 0,
+            Register::Data => self.mapping.rs_bit(),
+        }
+    }
+}
+
+impl<I2C: I2c> LcdBus for Pcf8574<I2C> {
+    type Error = I2C::Error;
+
+    fn write_nibble(
+        &mut self,
+        value: u8,
+        register: Register,
+        _wait: &mut impl WaitStrategy,
+    ) -> Result<(), Self::Error> {
+        let pins = self.mapping.data_bits(value) | self.register_bit(register);
+        self.write(&self.nibble(pins))
+    }
+
+    fn write_byte(
+        &mut self,
+        value: u8,
+        register: Register,
+        _wait: &mut impl WaitStrategy,
+    ) -> Result<(), Self::Error> {
+        let high_bits: u8 = self.mapping.data_bits(value);
+        let low_bits: u8 = self.mapping.data_bits(value << 4);
+        let [a, b, c] = self.nibble(high_bits | self.register_bit(register));
+        let [d, e, f] = self.nibble(low_bits | self.register_bit(register));
+        // Both nibbles in a single transfer
+        self.write(&[a, b, c, d, e, f])
+    }
+
+    fn set_backlight(&mut self, backlight: Backlight) -> Result<(), Self::Error> {
+        self.backlight = backlight;
+        let lines = self.with_backlight();
+        if !self.backlight_pin || self.last == Some(lines) {
+            return Ok(());
+        }
+        self.write(&[lines])
+    }
+
+    fn check(&mut self, backlight: Backlight) -> Result<(), Self::Error> {
+        self.backlight = backlight;
+        let lines = self.with_backlight();
+        self.write(&[lines])
+    }
+
+    fn read_byte(
+        &mut self,
+        register: Register,
+        _wait: &mut impl WaitStrategy,
+    ) -> Result<Option<u8>, Self::Error> {
+        // Data pins high, so the expander lets the display drive them
+        let read =
+            self.mapping.data_bits(0xf0) | self.mapping.rw_bit() | self.register_bit(register);
+
+        let mut value = 0;
+        // High nibble first
+        for shift in [0, 4] {
+            self.expander_write(read | self.enables)?;
+            let mut pins = [0];
+            self.i2c.read(self.address, &mut pins)?;
+            self.expander_write(read)?;
+            value |= self.mapping.data_value(pins[0]) >> shift;
+        }
+        Ok(Some(value))
+    }
+
+    fn controllers(&self) -> u8 {
+        if self.second_enable == 0 { 1 } else { 2 }
+    }
+
+    fn select(&mut self, controllers: Controllers) {
+        let first = self.mapping.en_bit();
+        self.enables = match controllers {
+            Controllers::First => first,
+            Controllers::Second => self.second_enable,
+            Controllers::Both => first | self.second_enable,
+        };
+    }
+
+    fn command_bytes(&self) -> u32 {
+        // Both nibbles, each set up and then clocked, in one transfer
+        6
+    }
+
+    fn bytes_written(&self) -> u32 {
+        self.bytes
+    }
+}
@@ -0,0 +1,225 @@
+//! Transport through half of a PCF8575 16-bit expander.
+
+use embedded_hal::i2c::I2c;
+
+use crate::{Address, Backlight, LcdBus, PinMapping, Polarity, Register, WaitStrategy};
+
+/// Port of a PCF8575 the display is wired to
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Half {
+    /// P00–P07
+    Low,
+    /// P10–P17
+    High,
+}
+
+/// A PCF8575 expander with the display on one of its two ports, by default
+/// wired as on a PCF8574 backpack: RS on bit 0, R/W on bit 1, E on bit 2, the
+/// backlight on bit 3 and D4–D7 on bits 4–7, see `pin_mapping`.
+///
+/// The expander is written two ports at a time, so the other port is kept as
+/// last set with `write_other`, all high at first, as after power up, which
+/// also lets its pins be used as inputs.
+pub struct Pcf8575<I2C> {
+    i2c: I2C,
+    address: u8,
+    half: Half,
+    mapping: PinMapping,
+    backlight: Backlight,
+    /// State of the port the display is not on
+    other: u8,
+    /// State of the display port as last written, unknown at first
+    last: Option<u8>,
+    /// Bytes written, wrapping
+    bytes: u32,
+}
+
+impl<I2C: I2c> Pcf8575<I2C> {
+    pub fn new(i2c: I2C, address: Address, half: Half) -> Self {
+        Pcf8575 {
+            i2c,
+            address: address.value(),
+            half,
+            mapping: PinMapping::PCF8574,
+            backlight: Backlight::On,
+            other: 0xff,
+            last: None,
+            bytes: 0,
+        }
+    }
+
+    /// For displays wired to other bits of the port, or with the backlight
+    /// driven active low, see `PinMapping`
+    pub fn pin_mapping(mut self, mapping: PinMapping) -> Self {
+        self.mapping = mapping;
+        self
+    }
+
+    /// I2C address of the expander
+    pub fn address(&self) -> Address {
+        Address::unchecked(self.address)
+    }
+
+    /// Give back the I2C bus
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+
+    /// State of the port the display is not on, as last set
+    pub fn other(&self) -> u8 {
+        self.other
+    }
+
+    /**
+    Set the pins of the port the display is not on, keeping the display lines
+    as they are. Pins set high can be read with `read_other`.
+
+    # Errors
+
+    Returns a `Result` that will report I2C errors, if any.
+    */
+    pub fn write_other(&mut self, value: u8) -> Result<(), I2C::Error> {
+        self.other = value;
+        self.write_ports(self.with_backlight())
+    }
+
+    /**
+    Read the pins of the port the display is not on.
+
+    # Errors
+
+    Returns a `Result` that will report I2C errors, if any.
+    */
+    pub fn read_other(&mut self) -> Result<u8, I2C::Error> {
+        let (_, other) = self.expander_read()?;
+        Ok(other)
+    }
+
+    // Put the high nibble of `value` on D4–D7 and clock it in
+    fn write4bits(
+        &mut self,
+        value: u8,
+        register: Register,
+        wait: &mut impl WaitStrategy,
+    ) -> Result<(), I2C::Error> {
+        let pins = self.mapping.data_bits(value) | self.register_bit(register);
+        self.expander_write(pins)?;
+        self.pulse_enable(pins, wait)
+    }
+
+    // Write the display port, with the backlight bit, along with the other one
+    fn expander_write(&mut self, pins: u8) -> Result<(), I2C::Error> {
+        self.write_ports(pins | self.backlight_bit())
+    }
+
+    fn write_ports(&mut self, display: u8) -> Result<(), I2C::Error> {
+        let ports = match self.half {
+            Half::Low => [display, self.other],
+            Half::High => [self.other, display],
+        };
+        self.i2c.write(self.address, &ports)?;
+        self.bytes = self.bytes.wrapping_add(2);
+        self.last = Some(display);
+        Ok(())
+    }
+
+    // The last state of the display port, with the backlight bit as now set
+    fn with_backlight(&self) -> u8 {
+        let lines = self.last.unwrap_or(0) & !self.mapping.backlight_bit();
+        lines | self.backlight_bit()
+    }
+
+    // Read the display port and the other one
+    fn expander_read(&mut self) -> Result<(u8, u8), I2C::Error> {
+        let mut ports = [0; 2];
+        self.i2c.read(self.address, &mut ports)?;
+        Ok(match self.half {
+            Half::Low => (ports[0], ports[1]),
+            Half::High => (ports[1], ports[0]),
+        })
+    }
+
+    fn pulse_enable(&mut self, pins: u8, wait: &mut impl WaitStrategy) -> Result<(), I2C::Error> {
+        let enable = self.mapping.en_bit();
+        self.expander_write(pins | enable)?; // En high
+        wait.wait_us(1);
+
+        self.expander_write(pins & !enable)?; // En low
+        wait.wait_us(1);
+
+        Ok(())
+    }
+
+    fn backlight_bit(&self) -> u8 {
+        match (self.backlight, self.mapping.backlight_polarity()) {
+            (Backlight::On, Polarity::ActiveHigh) | (Backlight::Off, Polarity::ActiveLow) => {
+                self.mapping.backlight_bit()
+            }
+            (Backlight::Off, Polarity::ActiveHigh) | (Backlight::On, Polarity::ActiveLow) => 0,
+        }
+    }
+
+    fn register_bit(&self, register: Register) -> u8 {
+        match register {
+            Register::Command => 0,
+            Register::Data => self.mapping.rs_bit(),
+        }
+    }
+}
+
+impl<I2C: I2c> LcdBus for Pcf8575<I2C> {
+    type Error = I2C::Error;
+
+    fn write_nibble(
+        &mut self,
+        value: u8,
+        register: Register,
+        wait: &mut impl WaitStrategy,
+    ) -> Result<(), Self::Error> {
+        self.write4bits(value, register, wait)
+    }
+
+    fn write_byte(
+        &mut self,
+        value: u8,
+        register: Register,
+        wait: &mut impl WaitStrategy,
+    ) -> Result<(), Self::Error> {
+        self.write4bits(value, register, wait)?;
+        self.write4bits(value << 4, register, wait)
+    }
+
+    fn set_backlight(&mut self, backlight: Backlight) -> Result<(), Self::Error> {
+        self.backlight = backlight;
+        self.write_ports(self.with_backlight())
+    }
+
+    fn read_byte(
+        &mut self,
+        register: Register,
+        _wait: &mut impl WaitStrategy,
+    ) -> Result<Option<u8>, Self::Error> {
+        // Data pins high, so the expander lets the display drive them
+        let read =
+            self.mapping.data_bits(0xf0) | self.mapping.rw_bit() | self.register_bit(register);
+
+        let mut value = 0;
+        // High nibble first
+        for shift in [0, 4] {
+            self.expander_write(read | self.mapping.en_bit())?;
+            let (pins, _) = self.expander_read()?;
+            self.expander_write(read)?;
+            value |= self.mapping.data_value(pins) >> shift;
+        }
+        Ok(Some(value))
+    }
+
+    fn command_bytes(&self) -> u32 {
+        // Three writes of both ports for each nibble
+        12
+    }
+
+    fn bytes_written(&self) -> u32 {
+        self.bytes
+    }
+}
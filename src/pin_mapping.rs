@@ -0,0 +1,112 @@
+//! Wiring of the display to the expander of a backpack.
+
+/// Level of the backlight bit that turns the backlight on
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Polarity {
+    ActiveHigh,
+    /// For backpacks whose backlight transistor is on while the bit is low
+    ActiveLow,
+}
+
+/// Expander bit, 0 to 7, each line of the display is wired to, see `new`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PinMapping {
+    rs: u8,
+    rw: u8,
+    en: u8,
+    backlight: u8,
+    /// D4 to D7, in order
+    data: [u8; 4],
+    backlight_polarity: Polarity,
+}
+
+impl PinMapping {
+    /// Layout of most PCF8574 backpacks, the one used by default: RS on P0,
+    /// R/W on P1, E on P2, the backlight on P3 and D4–D7 on P4–P7
+    pub const PCF8574: PinMapping = PinMapping::new(0, 1, 2, 3, [4, 5, 6, 7], Polarity::ActiveHigh);
+
+    /// Layout of mjkdz backpacks, with D4–D7 on P0–P3, E on P4, R/W on P5, RS
+    /// on P6 and the backlight on P7, active low
+    pub const MJKDZ: PinMapping = PinMapping::new(6, 5, 4, 7, [0, 1, 2, 3], Polarity::ActiveLow);
+
+    /**
+    Wire RS, R/W, E, the backlight and D4 to D7, in order, to the given
+    expander bits.
+
+    # Panics
+
+    Panics if a bit is above 7 or used by two lines, at compile time when
+    used in a `const`.
+    */
+    pub const fn new(
+        rs: u8,
+        rw: u8,
+        en: u8,
+        backlight: u8,
+        data: [u8; 4],
+        backlight_polarity: Polarity,
+    ) -> Self {
+        let bits = [rs, rw, en, backlight, data[0], data[1], data[2], data[3]];
+        let mut used = 0u8;
+        let mut i = 0;
+        while i < bits.len() {
+            assert!(bits[i] < 8, "expander bit above 7");
+            assert!(used & 1 << bits[i] == 0, "expander bit used twice");
+            used |= 1 << bits[i];
+            i += 1;
+        }
+
+        PinMapping {
+            rs,
+            rw,
+            en,
+            backlight,
+            data,
+            backlight_polarity,
+        }
+    }
+
+    pub(crate) const fn backlight_polarity(&self) -> Polarity {
+        self.backlight_polarity
+    }
+
+    pub(crate) const fn rs_bit(&self) -> u8 {
+        1 << self.rs
+    }
+
+    pub(crate) const fn rw_bit(&self) -> u8 {
+        1 << self.rw
+    }
+
+    pub(crate) const fn en_bit(&self) -> u8 {
+        1 << self.en
+    }
+
+    pub(crate) const fn backlight_bit(&self) -> u8 {
+        1 << self.backlight
+    }
+
+    /// Expander bits putting the high nibble of `value` on D4–D7
+    pub(crate) fn data_bits(&self, value: u8) -> u8 {
+        self.data
+            .iter()
+            .enumerate()
+            .filter(|(line, _)| value & (0x10 << line) != 0)
+            .fold(0, |bits, (_, bit)| bits | 1 << bit)
+    }
+
+    /// High nibble read on D4–D7 from the expander bits
+    pub(crate) fn data_value(&self, bits: u8) -> u8 {
+        self.data
+            .iter()
+            .enumerate()
+            .filter(|(_, bit)| bits & (1 << *bit) != 0)
+            .fold(0, |value, (line, _)| value | 0x10 << line)
+    }
+}
+
+impl Default for PinMapping {
+    fn default() -> Self {
+        PinMapping::PCF8574
+    }
+}
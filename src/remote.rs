@@ -0,0 +1,242 @@
+//! Mirroring of the screen over a serial link.
+//!
+//! Every frame is `0x7E`, a kind, a payload length, the payload and a checksum,
+//! the wrapping sum of the kind, length and payload bytes:
+//!
+//! - `G` (geometry): `cols`, `rows`, sent before anything else
+//! - `T` (text): `col`, `row`, then the ROM codes of consecutive cells
+
+use crate::{
+    Lcd, LcdBus, WaitStrategy,
+    buffer::{MAX_COLS, MAX_ROWS},
+};
+
+const START: u8 = 0x7E;
+const GEOMETRY: u8 = b'G';
+const TEXT: u8 = b'T';
+
+/// Longest payload of a frame: the position of a run and a full row
+const MAX_PAYLOAD: usize = MAX_COLS + 2;
+
+/// Sends what changed on a display since the last `sync` to an `embedded-io`
+/// writer, such as a UART, so the screen can be followed remotely with a
+/// `RemoteScreen`.
+pub struct RemoteMirror<W> {
+    writer: W,
+    // Screen as last sent, `None` until the geometry has been sent
+    sent: Option<[[u8; MAX_COLS]; MAX_ROWS]>,
+}
+
+impl<W: embedded_io::Write> RemoteMirror<W> {
+    pub fn new(writer: W) -> Self {
+        RemoteMirror { writer, sent: None }
+    }
+
+    /// Send the whole screen again on the next `sync`, e.g. after the viewer
+    /// restarted
+    pub fn resync(&mut self) {
+        self.sent = None;
+    }
+
+    /**
+    Send the cells of `lcd` that changed since the last call, according to its
+    shadow buffer. The first call sends the geometry and the whole screen.
+
+    # Errors
+
+    Returns a `Result` that will report errors of the writer, if any.
+    */
+    pub fn sync<B: LcdBus, D: WaitStrategy>(&mut self, lcd: &Lcd<B, D>) -> Result<(), W::Error> {
+        let cols = lcd.cols.min(MAX_COLS as u8);
+        let rows = lcd.rows.min(MAX_ROWS as u8);
+
+        let sent = match &mut self.sent {
+            Some(sent) => sent,
+            None => {
+                send_frame(&mut self.writer, GEOMETRY, &[cols, rows])?;
+                // Nothing the display can show, so every cell is sent
+                self.sent.insert([[0; MAX_COLS]; MAX_ROWS])
+            }
+        };
+
+        for row in 0..rows {
+            let Some(cells) = lcd.buffer.row(row) else {
+                break;
+            };
+            let previous = &mut sent[row as usize];
+
+            let mut col = 0;
+            while col < cols as usize {
+                if cells[col] == previous[col] {
+                    col += 1;
+                    continue;
+                }
+                let start = col;
+                while col < cols as usize && cells[col] != previous[col] {
+                    col += 1;
+                }
+
+                let mut payload = [0; MAX_PAYLOAD];
+                payload[0] = start as u8;
+                payload[1] = row;
+                payload[2..2 + col - start].copy_from_slice(&cells[start..col]);
+                send_frame(&mut self.writer, TEXT, &payload[..2 + col - start])?;
+                previous[start..col].copy_from_slice(&cells[start..col]);
+            }
+        }
+
+        self.writer.flush()
+    }
+
+    pub fn release(self) -> W {
+        self.writer
+    }
+}
+
+fn send_frame<W: embedded_io::Write>(
+    writer: &mut W,
+    kind: u8,
+    payload: &[u8],
+) -> Result<(), W::Error> {
+    let len = payload.len() as u8;
+    let checksum = payload
+        .iter()
+        .fold(kind.wrapping_add(len), |sum, byte| sum.wrapping_add(*byte));
+
+    writer.write_all(&[START, kind, len])?;
+    writer.write_all(payload)?;
+    writer.write_all(&[checksum])
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Decoding {
+    Start,
+    Kind,
+    Len,
+    Payload,
+    Checksum,
+}
+
+/// Rebuilds the screen sent by a `RemoteMirror` from the bytes received.
+///
+/// Bytes may be fed as they arrive, the decoder waits for the next frame start
+/// after a corrupted frame.
+pub struct RemoteScreen {
+    cols: u8,
+    rows: u8,
+    cells: [[u8; MAX_COLS]; MAX_ROWS],
+    state: Decoding,
+    kind: u8,
+    len: u8,
+    payload: [u8; MAX_PAYLOAD],
+    received: usize,
+}
+
+impl RemoteScreen {
+    pub const fn new() -> Self {
+        RemoteScreen {
+            cols: 0,
+            rows: 0,
+            cells: [[b' '; MAX_COLS]; MAX_ROWS],
+            state: Decoding::Start,
+            kind: 0,
+            len: 0,
+            payload: [0; MAX_PAYLOAD],
+            received: 0,
+        }
+    }
+
+    /// Number of columns, 0 until the geometry was received
+    pub fn cols(&self) -> u8 {
+        self.cols
+    }
+
+    /// Number of rows, 0 until the geometry was received
+    pub fn rows(&self) -> u8 {
+        self.rows
+    }
+
+    /// ROM codes of the given row
+    pub fn row(&self, row: u8) -> Option<&[u8]> {
+        if row >= self.rows {
+            return None;
+        }
+        Some(&self.cells[row as usize][..self.cols as usize])
+    }
+
+    /// Decode one received byte. Returns `true` when it completed a frame that
+    /// changed the screen.
+    pub fn feed(&mut self, byte: u8) -> bool {
+        match self.state {
+            Decoding::Start => {
+                if byte == START {
+                    self.state = Decoding::Kind;
+                }
+            }
+            Decoding::Kind => {
+                self.kind = byte;
+                self.state = Decoding::Len;
+            }
+            Decoding::Len => {
+                if byte as usize > MAX_PAYLOAD {
+                    self.state = Decoding::Start;
+                } else {
+                    self.len = byte;
+                    self.received = 0;
+                    self.state = if byte == 0 {
+                        Decoding::Checksum
+                    } else {
+                        Decoding::Payload
+                    };
+                }
+            }
+            Decoding::Payload => {
+                self.payload[self.received] = byte;
+                self.received += 1;
+                if self.received == self.len as usize {
+                    self.state = Decoding::Checksum;
+                }
+            }
+            Decoding::Checksum => {
+                self.state = Decoding::Start;
+                let payload = &self.payload[..self.len as usize];
+                let checksum = payload
+                    .iter()
+                    .fold(self.kind.wrapping_add(self.len), |sum, byte| {
+                        sum.wrapping_add(*byte)
+                    });
+                if checksum == byte {
+                    return self.apply();
+                }
+            }
+        }
+        false
+    }
+
+    fn apply(&mut self) -> bool {
+        let payload = &self.payload[..self.len as usize];
+        match (self.kind, payload) {
+            (GEOMETRY, [cols, rows]) => {
+                self.cols = (*cols).min(MAX_COLS as u8);
+                self.rows = (*rows).min(MAX_ROWS as u8);
+                self.cells = [[b' '; MAX_COLS]; MAX_ROWS];
+                true
+            }
+            (TEXT, [col, row, codes @ ..]) if *row < self.rows => {
+                let col = *col as usize;
+                let end = (col + codes.len()).min(self.cols as usize);
+                if col < end {
+                    self.cells[*row as usize][col..end].copy_from_slice(&codes[..end - col]);
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Default for RemoteScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,99 @@
+//! RGB backlights.
+
+use embedded_hal::i2c::I2c;
+
+/// A backlight whose color can be set
+pub trait RgbBacklight {
+    type Error;
+
+    /// Set the brightness of the red, green and blue LEDs, from 0 (off) to 255
+    fn set_rgb(&mut self, red: u8, green: u8, blue: u8) -> Result<(), Self::Error>;
+}
+
+// Registers of the PCA9632 and PCA9633
+const MODE1: u8 = 0x00;
+const PWM0: u8 = 0x02;
+const GRPPWM: u8 = 0x06;
+const LEDOUT: u8 = 0x08;
+
+/// PCA9632 or PCA9633 4-channel PWM LED driver, as found on RGB backpacks
+pub struct Pca963x<I2C> {
+    i2c: I2C,
+    address: u8,
+    // LED output of the red, green and blue channels
+    channels: [u8; 3],
+}
+
+impl<I2C: I2c> Pca963x<I2C> {
+    /// Address of the PCA9633 on Grove RGB LCDs
+    pub const GROVE_ADDRESS: u8 = 0x62;
+
+    /// Driver for a PCA963x with red on LED2, green on LED1 and blue on LED0
+    pub fn new(i2c: I2C, address: u8) -> Self {
+        Self::with_channels(i2c, address, 2, 1, 0)
+    }
+
+    /// Driver for a PCA963x with the colors on the given LED outputs (0 - 3)
+    pub fn with_channels(i2c: I2C, address: u8, red: u8, green: u8, blue: u8) -> Self {
+        Pca963x {
+            i2c,
+            address,
+            channels: [red & 0x3, green & 0x3, blue & 0x3],
+        }
+    }
+
+    /**
+    Wake the oscillator up and let every output be dimmed both by its own PWM
+    and the group PWM.
+
+    # Errors
+
+    Returns a `Result` that will report I2C errors, if any.
+    */
+    pub fn init(&mut self) -> Result<(), I2C::Error> {
+        self.write_register(MODE1, 0x00)?;
+        self.write_register(GRPPWM, 0xff)?;
+        self.write_register(LEDOUT, 0xff)
+    }
+
+    /**
+    Set the duty cycle of one LED output (0 - 3).
+
+    # Errors
+
+    Returns a `Result` that will report I2C errors, if any.
+    */
+    pub fn set_channel(&mut self, channel: u8, duty: u8) -> Result<(), I2C::Error> {
+        self.write_register(PWM0 + (channel & 0x3), duty)
+    }
+
+    /**
+    Dim every output at once, on top of their own duty cycle.
+
+    # Errors
+
+    Returns a `Result` that will report I2C errors, if any.
+    */
+    pub fn set_group_dimming(&mut self, level: u8) -> Result<(), I2C::Error> {
+        self.write_register(GRPPWM, level)
+    }
+
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+
+    fn write_register(&mut self, register: u8, value: u8) -> Result<(), I2C::Error> {
+        self.i2c.write(self.address, &[register, value])
+    }
+}
+
+impl<I2C: I2c> RgbBacklight for Pca963x<I2C> {
+    type Error = I2C::Error;
+
+    fn set_rgb(&mut self, red: u8, green: u8, blue: u8) -> Result<(), Self::Error> {
+        let [r, g, b] = self.channels;
+        self.set_channel(r, red)?;
+        self.set_channel(g, green)?;
+        self.set_channel(b, blue)
+    }
+}
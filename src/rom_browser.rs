@@ -0,0 +1,94 @@
+//! Pages through the character ROM.
+
+use crate::{Error, Lcd, LcdBus, WaitStrategy};
+
+/// First code with a glyph, the lower ones being the custom characters
+const FIRST: u8 = 0x20;
+/// Columns taken by the code at the start of each row, as `A0:`
+const LABEL: u8 = 3;
+
+/// Shows the glyphs of the character ROM a page at a time, each row starting
+/// with the hex code of its first glyph, to find out which symbols a panel has.
+#[derive(Copy, Clone, Debug)]
+pub struct RomBrowser {
+    first: u8,
+}
+
+impl RomBrowser {
+    /// A browser on the page starting at 0x20
+    pub const fn new() -> Self {
+        RomBrowser { first: FIRST }
+    }
+
+    /// Code of the first glyph of the page
+    pub fn first(&self) -> u8 {
+        self.first
+    }
+
+    /**
+    Show the current page, replacing the whole screen. Only the cells that
+    change are sent, and when buffering they are only staged.
+
+    # Errors
+
+    Returns a `Result` that will report bus errors, if any.
+    */
+    pub fn show<B: LcdBus, D: WaitStrategy>(
+        &self,
+        lcd: &mut Lcd<B, D>,
+    ) -> Result<(), Error<B::Error>> {
+        const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+        let per_row = Self::per_row(lcd);
+        let mut code = Some(self.first);
+        for row in 0..lcd.rows {
+            let label = code.map_or(*b"   ", |code| {
+                [HEX[(code >> 4) as usize], HEX[(code & 0xf) as usize], b':']
+            });
+            for (col, value) in label.into_iter().enumerate() {
+                lcd.buffer.stage(col as u8, row, value);
+            }
+
+            for col in LABEL..LABEL + per_row {
+                lcd.buffer.stage(col, row, code.unwrap_or(b' '));
+                code = code.and_then(|code| code.checked_add(1));
+            }
+        }
+
+        lcd.flush_unless_buffering()
+    }
+
+    /// Move to the next page, back to the first one after the last
+    pub fn next_page<B: LcdBus, D: WaitStrategy>(&mut self, lcd: &Lcd<B, D>) {
+        let page = Self::page_size(lcd);
+        self.first = match self.first.checked_add(page) {
+            Some(first) => first,
+            None => FIRST,
+        };
+    }
+
+    /// Move to the previous page, on to the last one before the first
+    pub fn previous_page<B: LcdBus, D: WaitStrategy>(&mut self, lcd: &Lcd<B, D>) {
+        let page = Self::page_size(lcd);
+        self.first = if self.first == FIRST {
+            // Start of the page holding 0xff
+            FIRST + (0xff - FIRST) / page * page
+        } else {
+            self.first.saturating_sub(page).max(FIRST)
+        };
+    }
+
+    fn per_row<B: LcdBus, D: WaitStrategy>(lcd: &Lcd<B, D>) -> u8 {
+        lcd.cols.saturating_sub(LABEL).max(1)
+    }
+
+    fn page_size<B: LcdBus, D: WaitStrategy>(lcd: &Lcd<B, D>) -> u8 {
+        Self::per_row(lcd).saturating_mul(lcd.rows)
+    }
+}
+
+impl Default for RomBrowser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,23 @@
+//! DDRAM layout of the rows of a display.
+
+/// DDRAM address of the first column of each row.
+///
+/// The controller has two lines of 40 characters, at 0x00 and 0x40. Displays
+/// with four rows show the second half of each line as the third and fourth
+/// rows, starting right after the columns of the first two.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RowOffsets(pub [u8; 4]);
+
+impl RowOffsets {
+    pub const LCD_16X2: RowOffsets = RowOffsets([0x00, 0x40, 0x10, 0x50]);
+    pub const LCD_16X4: RowOffsets = RowOffsets([0x00, 0x40, 0x10, 0x50]);
+    pub const LCD_20X4: RowOffsets = RowOffsets([0x00, 0x40, 0x14, 0x54]);
+    pub const LCD_40X2: RowOffsets = RowOffsets([0x00, 0x40, 0x28, 0x68]);
+    /// US2066 and SSD1311 OLEDs in 4-line mode
+    pub const US2066_4_LINES: RowOffsets = RowOffsets([0x00, 0x20, 0x40, 0x60]);
+
+    /// Layout of most displays with `cols` columns, the one used by default
+    pub const fn for_cols(cols: u8) -> Self {
+        RowOffsets([0x00, 0x40, cols, 0x40u8.wrapping_add(cols)])
+    }
+}
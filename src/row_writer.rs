@@ -0,0 +1,56 @@
+//! Formatted output confined to a single row.
+
+use core::fmt;
+
+use crate::{Error, Lcd, LcdBus, WaitStrategy};
+
+/// A `core::fmt::Write` handle on one row of the display, returned by
+/// `Lcd::row_writer`.
+///
+/// The writer keeps its own column, starting at the first one, and moves the
+/// cursor back to where it was after each write, so several parts of a program
+/// can `write!` to their own row without caring where the cursor is. Text past
+/// the last column is dropped, and bus errors are reported as `fmt::Error`.
+pub struct RowWriter<'a, B, D> {
+    lcd: &'a mut Lcd<B, D>,
+    row: u8,
+    col: u8,
+}
+
+impl<'a, B: LcdBus, D: WaitStrategy> RowWriter<'a, B, D> {
+    pub(crate) fn new(lcd: &'a mut Lcd<B, D>, row: u8) -> Self {
+        RowWriter { lcd, row, col: 0 }
+    }
+
+    /// Row the writer writes to
+    pub fn row(&self) -> u8 {
+        self.row
+    }
+
+    /// Column the next character will be written to
+    pub fn col(&self) -> u8 {
+        self.col
+    }
+
+    fn write_chars(&mut self, s: &str) -> Result<(), Error<B::Error>> {
+        let (prev_col, prev_row) = self.lcd.position;
+        self.lcd.set_cursor_position(self.col, self.row)?;
+        for c in s.chars() {
+            if self.col >= self.lcd.cols {
+                break;
+            }
+            self.lcd.write(self.lcd.encode_char(c))?;
+            self.col += 1;
+        }
+        self.lcd.set_cursor_position(prev_col, prev_row)
+    }
+}
+
+impl<B: LcdBus, D: WaitStrategy> fmt::Write for RowWriter<'_, B, D> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.row >= self.lcd.rows || self.col >= self.lcd.cols {
+            return Ok(());
+        }
+        self.write_chars(s).map_err(|_| fmt::Error)
+    }
+}
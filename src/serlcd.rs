@@ -0,0 +1,129 @@
+//! SparkFun SerLCD boards, whose AVR takes characters and commands over I2C.
+
+use embedded_hal::i2c::I2c;
+
+use crate::{Backlight, Contrast, LcdBus, Register, RgbBacklight, WaitStrategy};
+
+/// Prefix of the commands sent on to the HD44780
+const COMMAND: u8 = 0xFE;
+/// Prefix of the settings of the board
+const SETTING: u8 = 0x7C;
+/// Setting taking the contrast as the next byte
+const CONTRAST: u8 = 0x18;
+/// Setting taking the red, green and blue brightness as the next bytes
+const RGB: u8 = b'+';
+
+/// A SerLCD board (OpenLCD firmware), which initializes the display itself
+/// and forwards it the HD44780 commands, and sets its contrast and backlight.
+///
+/// Codes 0x7C (`|`) and 0xFE start commands of the board, so they are shown
+/// as spaces. `set_backlight` turns the backlight on with the color last given
+/// to `set_rgb`, white at first, or off.
+pub struct SerLcd<I2C> {
+    i2c: I2C,
+    address: u8,
+    backlight: Backlight,
+    /// Color shown while the backlight is on
+    color: [u8; 3],
+    /// Bytes written, wrapping
+    bytes: u32,
+}
+
+impl<I2C: I2c> SerLcd<I2C> {
+    /// Address of SerLCD boards unless changed
+    pub const DEFAULT_ADDRESS: u8 = 0x72;
+
+    pub fn new(i2c: I2C, address: u8) -> Self {
+        SerLcd {
+            i2c,
+            address,
+            backlight: Backlight::On,
+            color: [0xff; 3],
+            bytes: 0,
+        }
+    }
+
+    /// Give back the I2C bus
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+
+    fn write_color(&mut self) -> Result<(), I2C::Error> {
+        let [red, green, blue] = match self.backlight {
+            Backlight::On => self.color,
+            Backlight::Off => [0; 3],
+        };
+        self.write(&[SETTING, RGB, red, green, blue])
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> Result<(), I2C::Error> {
+        self.i2c.write(self.address, bytes)?;
+        self.bytes = self.bytes.wrapping_add(bytes.len() as u32);
+        Ok(())
+    }
+}
+
+impl<I2C: I2c> Contrast for SerLcd<I2C> {
+    type Error = I2C::Error;
+
+    /// Lower levels give more contrast, 120 being the default of the board
+    fn set_contrast(&mut self, level: u8) -> Result<(), Self::Error> {
+        self.write(&[SETTING, CONTRAST, level])
+    }
+}
+
+impl<I2C: I2c> RgbBacklight for SerLcd<I2C> {
+    type Error = I2C::Error;
+
+    fn set_rgb(&mut self, red: u8, green: u8, blue: u8) -> Result<(), Self::Error> {
+        self.color = [red, green, blue];
+        self.write_color()
+    }
+}
+
+impl<I2C: I2c> LcdBus for SerLcd<I2C> {
+    type Error = I2C::Error;
+
+    fn write_nibble(
+        &mut self,
+        _value: u8,
+        _register: Register,
+        _wait: &mut impl WaitStrategy,
+    ) -> Result<(), Self::Error> {
+        // The board set the interface of the display up itself, sending it
+        // 8-bit function sets would break it
+        Ok(())
+    }
+
+    fn write_byte(
+        &mut self,
+        value: u8,
+        register: Register,
+        _wait: &mut impl WaitStrategy,
+    ) -> Result<(), Self::Error> {
+        match register {
+            Register::Command => self.write(&[COMMAND, value]),
+            Register::Data if value == COMMAND || value == SETTING => self.write(b" "),
+            Register::Data => self.write(&[value]),
+        }
+    }
+
+    fn set_backlight(&mut self, backlight: Backlight) -> Result<(), Self::Error> {
+        self.backlight = backlight;
+        self.write_color()
+    }
+
+    fn restore_contrast(&mut self, level: u8) -> Result<bool, Self::Error> {
+        self.set_contrast(level)?;
+        Ok(true)
+    }
+
+    fn command_bytes(&self) -> u32 {
+        // Commands take a prefix, characters do not
+        2
+    }
+
+    fn bytes_written(&self) -> u32 {
+        self.bytes
+    }
+}
@@ -0,0 +1,165 @@
+//! User-facing display settings, and their serialization for persistent storage.
+
+#[cfg(feature = "embedded-storage")]
+use core::fmt;
+
+use crate::{Backlight, Blink, Cursor, Display, RomVariant};
+
+/// Display settings that can be saved and restored across power cycles
+#[derive(Copy, Clone, Debug)]
+pub struct Settings {
+    pub display: Display,
+    pub cursor: Cursor,
+    pub blink: Blink,
+    pub backlight: Backlight,
+    /// Custom characters uploaded at each CGRAM location
+    pub glyphs: [Option<[u8; 8]>; 8],
+    /// Char shown by each custom character, if any, see `Lcd::load_glyphs`
+    pub custom: [Option<char>; 8],
+    pub rom: RomVariant,
+    /// Code printed for chars the ROM does not have
+    pub replacement: u8,
+    /// Contrast level last set, on controllers having a setting for it
+    pub contrast: Option<u8>,
+}
+
+/// Why loading settings failed
+#[cfg(feature = "embedded-storage")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SettingsError<B, S> {
+    /// The driver reported an error while applying the settings
+    Lcd(B),
+    /// The storage reported an error
+    Storage(S),
+    /// The stored bytes are not settings saved by this driver
+    Invalid,
+}
+
+const MAGIC: u8 = 0x4c;
+const VERSION: u8 = 2;
+
+/// Offsets of the charset and contrast, after the glyphs
+const ROM: usize = 4 + 8 * 8;
+const REPLACEMENT: usize = ROM + 1;
+const CUSTOM_MASK: usize = ROM + 2;
+const CONTRAST: usize = ROM + 3;
+const CUSTOM: usize = ROM + 5;
+
+impl Settings {
+    /// Number of bytes taken by serialized settings
+    pub const SIZE: usize = CUSTOM + 8 * 4;
+
+    /// Serialize the settings
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0; Self::SIZE];
+        bytes[0] = MAGIC;
+        bytes[1] = VERSION;
+        bytes[2] = self.display as u8 | self.cursor as u8 | self.blink as u8 | self.backlight as u8;
+
+        for (location, glyph) in self.glyphs.iter().enumerate() {
+            if let Some(glyph) = glyph {
+                bytes[3] |= 1 << location;
+                bytes[4 + location * 8..][..8].copy_from_slice(glyph);
+            }
+        }
+
+        bytes[ROM] = match self.rom {
+            RomVariant::A00 => 0,
+            RomVariant::A02 => 1,
+        };
+        bytes[REPLACEMENT] = self.replacement;
+        for (location, c) in self.custom.iter().enumerate() {
+            if let Some(c) = c {
+                bytes[CUSTOM_MASK] |= 1 << location;
+                bytes[CUSTOM + location * 4..][..4].copy_from_slice(&(*c as u32).to_le_bytes());
+            }
+        }
+        if let Some(level) = self.contrast {
+            bytes[CONTRAST] = 1;
+            bytes[CONTRAST + 1] = level;
+        }
+
+        bytes
+    }
+
+    /// Deserialize settings, returning `None` if the bytes were not produced by `to_bytes`
+    pub fn from_bytes(bytes: &[u8; Self::SIZE]) -> Option<Self> {
+        if bytes[0] != MAGIC || bytes[1] != VERSION {
+            return None;
+        }
+
+        let flags = bytes[2];
+        let is_set = |flag: u8| flags & flag != 0;
+
+        let mut glyphs = [None; 8];
+        for (location, glyph) in glyphs.iter_mut().enumerate() {
+            if bytes[3] & (1 << location) != 0 {
+                let mut charmap = [0; 8];
+                charmap.copy_from_slice(&bytes[4 + location * 8..][..8]);
+                *glyph = Some(charmap);
+            }
+        }
+
+        let mut custom = [None; 8];
+        for (location, c) in custom.iter_mut().enumerate() {
+            if bytes[CUSTOM_MASK] & (1 << location) != 0 {
+                let mut code = [0; 4];
+                code.copy_from_slice(&bytes[CUSTOM + location * 4..][..4]);
+                *c = Some(char::from_u32(u32::from_le_bytes(code))?);
+            }
+        }
+
+        let rom = match bytes[ROM] {
+            0 => RomVariant::A00,
+            1 => RomVariant::A02,
+            _ => return None,
+        };
+        let contrast = match bytes[CONTRAST] {
+            0 => None,
+            1 => Some(bytes[CONTRAST + 1]),
+            _ => return None,
+        };
+
+        Some(Settings {
+            display: if is_set(Display::On as u8) {
+                Display::On
+            } else {
+                Display::Off
+            },
+            cursor: if is_set(Cursor::On as u8) {
+                Cursor::On
+            } else {
+                Cursor::Off
+            },
+            blink: if is_set(Blink::On as u8) {
+                Blink::On
+            } else {
+                Blink::Off
+            },
+            backlight: if is_set(Backlight::On as u8) {
+                Backlight::On
+            } else {
+                Backlight::Off
+            },
+            glyphs,
+            custom,
+            rom,
+            replacement: bytes[REPLACEMENT],
+            contrast,
+        })
+    }
+}
+
+#[cfg(feature = "embedded-storage")]
+impl<B: fmt::Debug, S: fmt::Debug> fmt::Display for SettingsError<B, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettingsError::Lcd(error) => write!(f, "display error: {error:?}"),
+            SettingsError::Storage(error) => write!(f, "storage error: {error:?}"),
+            SettingsError::Invalid => f.write_str("no valid settings in storage"),
+        }
+    }
+}
+
+#[cfg(feature = "embedded-storage")]
+impl<B: fmt::Debug, S: fmt::Debug> core::error::Error for SettingsError<B, S> {}
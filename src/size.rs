@@ -0,0 +1,65 @@
+//! Geometry of a display fixed at compile time.
+
+use embedded_hal::i2c::I2c;
+
+use crate::{Address, Lcd, LcdBus, Pcf8574, RowOffsets, Uninitialized, WaitStrategy, buffer};
+
+/// A display of `COLS` columns and `ROWS` rows, checked at compile time: up to
+/// 40 columns, as many as a line of the controller holds, and up to 4 rows.
+///
+/// Only the geometry and positions are checked at compile time: the driver it
+/// creates is the same `Lcd` as `Lcd::with_bus` gives, with a shadow buffer
+/// holding the largest display whatever the size.
+///
+/// ```ignore
+/// let mut lcd = Lcd20x4::new(i2c, Address::PCF8574, delay).init()?;
+/// let (col, row) = Lcd20x4::position::<19, 3>();
+/// lcd.set_cursor_position(col, row)?;
+/// ```
+pub struct Size<const COLS: u8, const ROWS: u8>;
+
+/// The common 16x2 display
+pub type Lcd16x2 = Size<16, 2>;
+
+/// The common 20x4 display
+pub type Lcd20x4 = Size<20, 4>;
+
+impl<const COLS: u8, const ROWS: u8> Size<COLS, ROWS> {
+    pub const COLS: u8 = {
+        assert!(COLS > 0 && COLS as usize <= buffer::MAX_COLS);
+        COLS
+    };
+
+    pub const ROWS: u8 = {
+        assert!(ROWS > 0 && ROWS as usize <= buffer::MAX_ROWS);
+        ROWS
+    };
+
+    /// DDRAM layout of the rows, see `RowOffsets::for_cols`
+    pub const ROW_OFFSETS: RowOffsets = RowOffsets::for_cols(Self::COLS);
+
+    /// Create the driver for such a display behind a PCF8574 I2C backpack, see
+    /// `Lcd::new`
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new<I2C: I2c, D: WaitStrategy>(
+        i2c: I2C,
+        address: Address,
+        delay: D,
+    ) -> Lcd<Pcf8574<I2C>, D, Uninitialized> {
+        Self::with_bus(Pcf8574::new(i2c, address), delay)
+    }
+
+    /// Create the driver for such a display driven through `bus`, see
+    /// `Lcd::with_bus`
+    pub fn with_bus<B: LcdBus, D: WaitStrategy>(bus: B, delay: D) -> Lcd<B, D, Uninitialized> {
+        Lcd::with_bus(bus, delay, Self::COLS, Self::ROWS).row_offsets(Self::ROW_OFFSETS)
+    }
+
+    /// `COL`, `ROW`, checked at compile time to be on the display
+    pub const fn position<const COL: u8, const ROW: u8>() -> (u8, u8) {
+        const {
+            assert!(COL < COLS && ROW < ROWS);
+        }
+        (COL, ROW)
+    }
+}
@@ -0,0 +1,145 @@
+//! ST7032i COG controllers, spoken to over I2C.
+
+use embedded_hal::i2c::I2c;
+
+use crate::{Address, Backlight, BitMode, Contrast, LcdBus, NativeI2c, Register, WaitStrategy};
+
+/// Function set, 8-bit and two lines until the driver tells otherwise
+const FUNCTION: u8 = 0x38;
+/// Bit of the function set selecting the extended instructions
+const IS: u8 = 0x01;
+/// With IS set: low bits of the contrast, then power, icon and high bits
+const CONTRAST_LOW: u8 = 0x70;
+const POWER: u8 = 0x50;
+const BOOSTER: u8 = 0x04;
+/// With IS set: 1/5 bias, 183Hz oscillator
+const OSCILLATOR: u8 = 0x14;
+/// With IS set: voltage follower on, amplified ratio 4
+const FOLLOWER: u8 = 0x6C;
+
+/// A ST7032i controller, as on many small COG displays, taking HD44780
+/// commands over I2C, each after a control byte, in 8-bit mode.
+///
+/// `setup` starts the oscillator and the voltage follower, and sets the
+/// contrast last set, 0x28 out of 0x3F at first, with the internal booster
+/// needed at 3.3V, see `without_booster`. Most of them have no backlight, or
+/// one wired straight to the supply.
+pub struct St7032i<I2C> {
+    native: NativeI2c<I2C>,
+    /// Function set of the driver, with IS cleared
+    function: u8,
+    /// Contrast, 0 to 0x3F
+    contrast: u8,
+    booster: bool,
+}
+
+impl<I2C: I2c> St7032i<I2C> {
+    pub const DEFAULT_ADDRESS: u8 = 0x3E;
+
+    pub fn new(i2c: I2C, address: u8) -> Self {
+        St7032i {
+            native: NativeI2c::new(i2c, Address::unchecked(address)),
+            function: FUNCTION,
+            contrast: 0x28,
+            booster: true,
+        }
+    }
+
+    /// For displays powered at 5V, which do not need the internal booster
+    pub fn without_booster(mut self) -> Self {
+        self.booster = false;
+        self
+    }
+
+    /// Give back the I2C bus
+    pub fn release(self) -> I2C {
+        self.native.release()
+    }
+
+    fn command(&mut self, value: u8) -> Result<(), I2C::Error> {
+        self.native.command(value)
+    }
+
+    // Function set keeping the lines and font of the driver, selecting the
+    // normal or extended instructions
+    fn function_set(&mut self, extended: bool) -> Result<(), I2C::Error> {
+        let is = if extended { IS } else { 0 };
+        self.command(self.function | is)
+    }
+
+    // Send the contrast and power settings, IS being set
+    fn write_contrast(&mut self) -> Result<(), I2C::Error> {
+        let booster = if self.booster { BOOSTER } else { 0 };
+        self.command(CONTRAST_LOW | (self.contrast & 0x0f))?;
+        self.command(POWER | booster | (self.contrast >> 4))
+    }
+}
+
+impl<I2C: I2c> Contrast for St7032i<I2C> {
+    type Error = I2C::Error;
+
+    fn set_contrast(&mut self, level: u8) -> Result<(), Self::Error> {
+        self.contrast = level >> 2;
+        self.function_set(true)?;
+        self.write_contrast()?;
+        self.function_set(false)
+    }
+}
+
+impl<I2C: I2c> LcdBus for St7032i<I2C> {
+    type Error = I2C::Error;
+
+    fn write_nibble(
+        &mut self,
+        value: u8,
+        register: Register,
+        wait: &mut impl WaitStrategy,
+    ) -> Result<(), Self::Error> {
+        self.native.write_nibble(value, register, wait)
+    }
+
+    fn write_byte(
+        &mut self,
+        value: u8,
+        register: Register,
+        wait: &mut impl WaitStrategy,
+    ) -> Result<(), Self::Error> {
+        self.native.write_byte(value, register, wait)
+    }
+
+    fn set_backlight(&mut self, _backlight: Backlight) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn setup(&mut self, wait: &mut impl WaitStrategy) -> Result<(), Self::Error> {
+        self.function_set(false)?;
+        self.function_set(true)?;
+        self.command(OSCILLATOR)?;
+        self.write_contrast()?;
+        self.command(FOLLOWER)?;
+        // For the power to stabilize
+        wait.wait_ms(200);
+        self.function_set(false)
+    }
+
+    fn set_function(&mut self, function: u8) {
+        self.function = function & !IS;
+    }
+
+    fn bit_mode(&self) -> BitMode {
+        BitMode::Bit8
+    }
+
+    fn restore_contrast(&mut self, level: u8) -> Result<bool, Self::Error> {
+        self.set_contrast(level)?;
+        Ok(true)
+    }
+
+    fn command_bytes(&self) -> u32 {
+        self.native.command_bytes()
+    }
+
+    fn bytes_written(&self) -> u32 {
+        self.native.bytes_written()
+    }
+}
@@ -0,0 +1,90 @@
+//! Metrics about flushes of the shadow buffer.
+
+/// What a single flush cost
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct FlushStats {
+    /// Cells rewritten on the display
+    pub cells: u32,
+    /// Bytes written on the bus, as counted by `LcdBus::bytes_written`
+    pub bytes: u32,
+    /// Time spent, in microseconds. Only known when a clock is provided with `Lcd::set_clock`
+    pub micros: Option<u32>,
+}
+
+/// Per-flush metrics and their rolling averages
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct FrameStats {
+    /// Number of flushes since the statistics were last reset
+    pub flushes: u32,
+    /// Metrics of the last flush
+    pub last: FlushStats,
+    /// Exponential moving average of the metrics, mostly weighted over the last 8 flushes
+    pub average: FlushStats,
+}
+
+impl FrameStats {
+    pub(crate) fn record(&mut self, flush: FlushStats) {
+        if self.flushes == 0 {
+            self.average = flush;
+        } else {
+            self.average = FlushStats {
+                cells: moving_average(self.average.cells, flush.cells),
+                bytes: moving_average(self.average.bytes, flush.bytes),
+                micros: match (self.average.micros, flush.micros) {
+                    (Some(average), Some(micros)) => Some(moving_average(average, micros)),
+                    (_, micros) => micros,
+                },
+            };
+        }
+        self.last = flush;
+        self.flushes = self.flushes.saturating_add(1);
+    }
+}
+
+fn moving_average(average: u32, value: u32) -> u32 {
+    let average = average as i64;
+    (average + (value as i64 - average) / 8) as u32
+}
+
+/// Histogram of the time taken to send individual bytes to the display
+#[cfg(feature = "latency-histogram")]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct LatencyHistogram {
+    counts: [u32; LatencyHistogram::BUCKETS],
+    max: u32,
+}
+
+#[cfg(feature = "latency-histogram")]
+impl LatencyHistogram {
+    /// Number of buckets
+    pub const BUCKETS: usize = 8;
+    /// Inclusive upper bound, in microseconds, of each bucket but the last one,
+    /// which counts everything slower
+    pub const BOUNDS: [u32; Self::BUCKETS - 1] = [50, 100, 200, 500, 1_000, 2_000, 5_000];
+
+    pub(crate) fn record(&mut self, micros: u32) {
+        let bucket = Self::BOUNDS
+            .iter()
+            .position(|bound| micros <= *bound)
+            .unwrap_or(Self::BUCKETS - 1);
+        self.counts[bucket] = self.counts[bucket].saturating_add(1);
+        self.max = self.max.max(micros);
+    }
+
+    /// Number of writes that fell in each bucket
+    pub fn counts(&self) -> &[u32; Self::BUCKETS] {
+        &self.counts
+    }
+
+    /// Total number of writes recorded
+    pub fn total(&self) -> u32 {
+        self.counts
+            .iter()
+            .fold(0, |total, count| total.saturating_add(*count))
+    }
+
+    /// Slowest write recorded, in microseconds
+    pub fn max(&self) -> u32 {
+        self.max
+    }
+}
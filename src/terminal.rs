@@ -0,0 +1,100 @@
+//! Scrolling terminal output, for command line interfaces such as the `menu` crate.
+
+use crate::{Error, Lcd, LcdBus, WaitStrategy, buffer};
+
+/// Turns the display into an `embedded_io::Write` output that behaves like a
+/// small terminal: text wraps at the last column, `\r` returns to the start of
+/// the line, backspace moves one column back, and a new line on the last row
+/// scrolls the whole screen up.
+///
+/// This is what the `menu` crate expects as the interface of its `Runner`, so
+/// serial menus can be shown on the display.
+pub struct Terminal<B, D> {
+    lcd: Lcd<B, D>,
+}
+
+impl<B: LcdBus, D: WaitStrategy> Terminal<B, D> {
+    pub fn new(lcd: Lcd<B, D>) -> Self {
+        Terminal { lcd }
+    }
+
+    pub fn lcd(&mut self) -> &mut Lcd<B, D> {
+        &mut self.lcd
+    }
+
+    /// Give back the display
+    pub fn release(self) -> Lcd<B, D> {
+        self.lcd
+    }
+
+    fn put(&mut self, byte: u8) -> Result<(), Error<B::Error>> {
+        let (col, row) = self.lcd.position;
+        match byte {
+            b'\n' => self.new_line(),
+            b'\r' => self.lcd.set_cursor_position(0, row),
+            // Backspace
+            0x08 => self.lcd.set_cursor_position(col.saturating_sub(1), row),
+            _ => {
+                if col >= self.lcd.cols {
+                    self.new_line()?;
+                }
+                self.lcd.write(byte)
+            }
+        }
+    }
+
+    fn new_line(&mut self) -> Result<(), Error<B::Error>> {
+        let row = self.lcd.position.1;
+        if row + 1 < self.lcd.rows {
+            return self.lcd.set_cursor_position(0, row + 1);
+        }
+
+        self.scroll_up()?;
+        self.lcd.set_cursor_position(0, row)
+    }
+
+    // Move every row up by one, using the shadow buffer, and blank the last one
+    fn scroll_up(&mut self) -> Result<(), Error<B::Error>> {
+        let cols = self.lcd.cols.min(buffer::MAX_COLS as u8) as usize;
+        let last = self.lcd.rows - 1;
+
+        for row in 1..=last {
+            let Some(cells) = self.lcd.buffer.row(row).copied() else {
+                break;
+            };
+            self.lcd.set_cursor_position(0, row - 1)?;
+            for code in &cells[..cols] {
+                self.lcd.write(*code)?;
+            }
+        }
+
+        self.lcd.set_cursor_position(0, last)?;
+        for _ in 0..cols {
+            self.lcd.write(b' ')?;
+        }
+        Ok(())
+    }
+}
+
+impl<E: core::fmt::Debug> embedded_io::Error for Error<E> {
+    fn kind(&self) -> embedded_io::ErrorKind {
+        embedded_io::ErrorKind::Other
+    }
+}
+
+impl<B: LcdBus, D: WaitStrategy> embedded_io::ErrorType for Terminal<B, D> {
+    type Error = Error<B::Error>;
+}
+
+impl<B: LcdBus, D: WaitStrategy> embedded_io::Write for Terminal<B, D> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        for byte in buf {
+            self.put(*byte)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.lcd.flush()
+    }
+}
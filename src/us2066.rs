@@ -0,0 +1,155 @@
+//! US2066 and SSD1311 character OLED controllers, spoken to over I2C.
+
+use embedded_hal::i2c::I2c;
+
+use crate::{Address, Backlight, BitMode, Contrast, LcdBus, NativeI2c, Register, WaitStrategy};
+
+/// Function set with RE set, giving access to the extended instructions
+const EXTENDED: u8 = 0x2A;
+/// Function set with RE cleared, back to the HD44780 instructions
+const FUNDAMENTAL: u8 = 0x28;
+/// With RE set, enter and leave the OLED command set
+const OLED_ON: u8 = 0x79;
+const OLED_OFF: u8 = 0x78;
+/// With the OLED command set, taking the contrast as the next command
+const SET_CONTRAST: u8 = 0x81;
+
+/// A US2066 or SSD1311 controller, as on Newhaven and Winstar character OLEDs,
+/// taking HD44780 commands over I2C, each after a control byte, in 8-bit mode.
+///
+/// `setup` powers the panel up: internal regulator, oscillator, segment and
+/// COM settings, and the contrast last set, 0x7F at first. Four-line panels
+/// address their rows 0x20 apart, see `RowOffsets::US2066_4_LINES`. There is
+/// no backlight: turn the display off instead.
+pub struct Us2066<I2C> {
+    native: NativeI2c<I2C>,
+    rows: u8,
+    contrast: u8,
+}
+
+impl<I2C: I2c> Us2066<I2C> {
+    /// Address with SA0 low, 0x3D with it high
+    pub const DEFAULT_ADDRESS: u8 = 0x3C;
+
+    /// Driver for a panel of `rows` rows
+    pub fn new(i2c: I2C, address: u8, rows: u8) -> Self {
+        Us2066 {
+            native: NativeI2c::new(i2c, Address::unchecked(address)),
+            rows,
+            contrast: 0x7F,
+        }
+    }
+
+    /// Give back the I2C bus
+    pub fn release(self) -> I2C {
+        self.native.release()
+    }
+
+    fn command(&mut self, value: u8) -> Result<(), I2C::Error> {
+        self.native.command(value)
+    }
+
+    // Run `commands` within the OLED command set
+    fn oled_commands(&mut self, commands: &[u8]) -> Result<(), I2C::Error> {
+        self.command(EXTENDED)?;
+        self.command(OLED_ON)?;
+        for command in commands {
+            self.command(*command)?;
+        }
+        self.command(OLED_OFF)?;
+        self.command(FUNDAMENTAL)
+    }
+}
+
+impl<I2C: I2c> Contrast for Us2066<I2C> {
+    type Error = I2C::Error;
+
+    fn set_contrast(&mut self, level: u8) -> Result<(), Self::Error> {
+        self.contrast = level;
+        self.oled_commands(&[SET_CONTRAST, level])
+    }
+}
+
+impl<I2C: I2c> LcdBus for Us2066<I2C> {
+    type Error = I2C::Error;
+
+    fn write_nibble(
+        &mut self,
+        value: u8,
+        register: Register,
+        wait: &mut impl WaitStrategy,
+    ) -> Result<(), Self::Error> {
+        self.native.write_nibble(value, register, wait)
+    }
+
+    fn write_byte(
+        &mut self,
+        value: u8,
+        register: Register,
+        wait: &mut impl WaitStrategy,
+    ) -> Result<(), Self::Error> {
+        self.native.write_byte(value, register, wait)
+    }
+
+    fn set_backlight(&mut self, _backlight: Backlight) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn setup(&mut self, wait: &mut impl WaitStrategy) -> Result<(), Self::Error> {
+        // Internal regulator on, through function selection A
+        self.command(EXTENDED)?;
+        self.command(0x71)?;
+        self.native.data(0x5C)?;
+        self.command(FUNDAMENTAL)?;
+        // Display off while setting up
+        self.command(0x08)?;
+
+        // Oscillator frequency and clock divider
+        self.oled_commands(&[0xD5, 0x70])?;
+
+        // Extended function set: 5-dot font, 3 or 4-line mode
+        self.command(EXTENDED)?;
+        let lines = if self.rows > 2 { 0x09 } else { 0x08 };
+        self.command(lines)?;
+        // COM and segment scan directions
+        self.command(0x06)?;
+        // Function selection B: ROM A, 8 CGRAM characters
+        self.command(0x72)?;
+        self.native.data(0x00)?;
+        self.command(FUNDAMENTAL)?;
+
+        // Segment pins, VSL, pre-charge period, VCOMH deselect level
+        let contrast = self.contrast;
+        self.oled_commands(&[
+            0xDA,
+            0x10,
+            0xDC,
+            0x00,
+            SET_CONTRAST,
+            contrast,
+            0xD9,
+            0xF1,
+            0xDB,
+            0x40,
+        ])?;
+        wait.wait_ms(100);
+        Ok(())
+    }
+
+    fn bit_mode(&self) -> BitMode {
+        BitMode::Bit8
+    }
+
+    fn restore_contrast(&mut self, level: u8) -> Result<bool, Self::Error> {
+        self.set_contrast(level)?;
+        Ok(true)
+    }
+
+    fn command_bytes(&self) -> u32 {
+        self.native.command_bytes()
+    }
+
+    fn bytes_written(&self) -> u32 {
+        self.native.bytes_written()
+    }
+}
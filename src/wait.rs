@@ -0,0 +1,93 @@
+//! How the driver waits for the display.
+
+use embedded_hal::delay::DelayNs;
+
+/// Waits for the display between operations.
+///
+/// Every `DelayNs` is a `WaitStrategy`, so a delay can be passed as before.
+/// `BusyFlag` polls the busy flag of the controller instead of waiting for
+/// the worst case execution time of commands, and `Callback` hands the waits
+/// to a function, for example one yielding to an RTOS scheduler.
+pub trait WaitStrategy {
+    /// Wait for at least `us` microseconds
+    fn wait_us(&mut self, us: u32);
+
+    /// Wait for at least `ms` milliseconds
+    fn wait_ms(&mut self, ms: u32) {
+        for _ in 0..ms {
+            self.wait_us(1_000);
+        }
+    }
+
+    /// Whether the driver polls the busy flag after commands, instead of
+    /// waiting for their execution time
+    fn polls_busy_flag(&self) -> bool {
+        false
+    }
+}
+
+impl<T: DelayNs> WaitStrategy for T {
+    fn wait_us(&mut self, us: u32) {
+        self.delay_us(us);
+    }
+
+    fn wait_ms(&mut self, ms: u32) {
+        self.delay_ms(ms);
+    }
+}
+
+/// Polls the busy flag of the controller after every command and character,
+/// which needs the R/W pin of the display to be wired to the expander.
+///
+/// The delay is still used during initialization, before the busy flag can
+/// be read. Clearing the display and returning home, which otherwise wait for
+/// 2ms, are then only as slow as the controller. When the flag stays set for
+/// 100 reads, as it does if R/W is not wired, `Error::Busy` is reported.
+pub struct BusyFlag<D> {
+    delay: D,
+}
+
+impl<D: DelayNs> BusyFlag<D> {
+    pub fn new(delay: D) -> Self {
+        BusyFlag { delay }
+    }
+
+    pub fn release(self) -> D {
+        self.delay
+    }
+}
+
+impl<D: DelayNs> WaitStrategy for BusyFlag<D> {
+    fn wait_us(&mut self, us: u32) {
+        self.delay.delay_us(us);
+    }
+
+    fn wait_ms(&mut self, ms: u32) {
+        self.delay.delay_ms(ms);
+    }
+
+    fn polls_busy_flag(&self) -> bool {
+        true
+    }
+}
+
+/// Calls a function with the number of microseconds to wait
+pub struct Callback<F> {
+    wait: F,
+}
+
+impl<F: FnMut(u32)> Callback<F> {
+    pub fn new(wait: F) -> Self {
+        Callback { wait }
+    }
+}
+
+impl<F: FnMut(u32)> WaitStrategy for Callback<F> {
+    fn wait_us(&mut self, us: u32) {
+        (self.wait)(us);
+    }
+
+    fn wait_ms(&mut self, ms: u32) {
+        (self.wait)(ms.saturating_mul(1_000));
+    }
+}
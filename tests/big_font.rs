@@ -0,0 +1,28 @@
+mod common;
+
+use common::{NoDelay, Panel};
+use i2c_lcd_screen::{BigFont, Lcd, RomVariant};
+
+#[test]
+fn full_block_comes_from_the_a00_rom() {
+    let panel = Panel::new(1);
+    let mut lcd = Lcd::with_bus(panel.clone(), NoDelay, 16, 2).init().unwrap();
+
+    BigFont::new().print(&mut lcd, 0, 0, "1").unwrap();
+    assert_eq!(panel.controller(0, |c| c.ddram[0x41]), 0xff);
+}
+
+#[test]
+fn full_block_is_uploaded_on_the_a02_rom() {
+    let panel = Panel::new(1);
+    let mut lcd = Lcd::with_bus(panel.clone(), NoDelay, 16, 2).init().unwrap();
+    lcd.set_rom_variant(RomVariant::A02);
+
+    BigFont::new().print(&mut lcd, 0, 0, "1").unwrap();
+    let location = panel.controller(0, |c| c.ddram[0x41]) as usize;
+    assert!(location < 8);
+    assert_eq!(
+        panel.controller(0, |c| c.cgram[location * 8..][..8].to_vec()),
+        [0x1f; 8]
+    );
+}
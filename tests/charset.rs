@@ -0,0 +1,16 @@
+mod common;
+
+use common::{NoDelay, Panel};
+use i2c_lcd_screen::{Lcd, RomVariant};
+
+#[test]
+fn detect_rom_variant_shows_the_glyph_while_buffering() {
+    let panel = Panel::new(1);
+    let mut lcd = Lcd::with_bus(panel.clone(), NoDelay, 16, 2).init().unwrap();
+    lcd.set_buffering(true).unwrap();
+
+    let rom = lcd
+        .detect_rom_variant(|| panel.text(0, 6) == "Yen? \\")
+        .unwrap();
+    assert_eq!(rom, RomVariant::A00);
+}
@@ -0,0 +1,261 @@
+//! Test doubles shared by the integration tests: a bus emulating the DDRAM and
+//! CGRAM of HD44780 controllers, an I2C bus recording what is written, output
+//! pins logging their levels, and a delay that does not wait.
+
+#![allow(dead_code)]
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::vec::Vec;
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{self, OutputPin};
+use embedded_hal::i2c::{self, I2c, Operation};
+use i2c_lcd_screen::{Backlight, Controllers, LcdBus, Register, WaitStrategy};
+
+/// Delay returning right away
+pub struct NoDelay;
+
+impl DelayNs for NoDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+/// State of one emulated controller
+pub struct Controller {
+    pub ddram: [u8; 128],
+    pub cgram: [u8; 64],
+    pub address: u8,
+    /// Whether data goes to CGRAM, after a CGRAM address was set
+    pub cgram_mode: bool,
+    /// Whether the address counter moves down after each write
+    pub decrement: bool,
+    /// Every command byte received, in order
+    pub commands: Vec<u8>,
+    /// High nibbles received alone, as during initialization
+    pub nibbles: Vec<u8>,
+}
+
+impl Controller {
+    fn new() -> Self {
+        Controller {
+            ddram: [b' '; 128],
+            cgram: [0; 64],
+            address: 0,
+            cgram_mode: false,
+            decrement: false,
+            commands: Vec::new(),
+            nibbles: Vec::new(),
+        }
+    }
+
+    fn command(&mut self, value: u8) {
+        self.commands.push(value);
+        if value & 0x80 != 0 {
+            self.address = value & 0x7f;
+            self.cgram_mode = false;
+        } else if value & 0x40 != 0 {
+            self.address = value & 0x3f;
+            self.cgram_mode = true;
+        } else if value & 0x04 != 0 && value & 0xf8 == 0 {
+            self.decrement = value & 0x02 == 0;
+        } else if value & 0xfe == 0x02 {
+            self.address = 0;
+            self.cgram_mode = false;
+        } else if value == 0x01 {
+            self.ddram = [b' '; 128];
+            self.address = 0;
+            self.cgram_mode = false;
+            self.decrement = false;
+        }
+    }
+
+    fn data(&mut self, value: u8) {
+        if self.cgram_mode {
+            self.cgram[self.address as usize] = value;
+            self.address = self.step(self.address) & 0x3f;
+        } else {
+            self.ddram[self.address as usize] = value;
+            self.address = self.step(self.address) & 0x7f;
+        }
+    }
+
+    fn step(&self, address: u8) -> u8 {
+        if self.decrement {
+            address.wrapping_sub(1)
+        } else {
+            address.wrapping_add(1)
+        }
+    }
+
+    /// `len` DDRAM bytes from `address`, as text
+    pub fn text(&self, address: u8, len: usize) -> std::string::String {
+        let start = address as usize;
+        self.ddram[start..start + len]
+            .iter()
+            .map(|&b| b as char)
+            .collect()
+    }
+
+    /// Function set commands received, with the interface bits
+    pub fn function_sets(&self) -> Vec<u8> {
+        self.commands
+            .iter()
+            .copied()
+            .filter(|c| c & 0xe0 == 0x20)
+            .collect()
+    }
+}
+
+pub struct PanelState {
+    pub controllers: Vec<Controller>,
+    selected: Controllers,
+    pub backlight: Option<Backlight>,
+}
+
+/// Bus emulating one or two HD44780 controllers, shared with the test so its
+/// state can be inspected while the driver owns it
+#[derive(Clone)]
+pub struct Panel(pub Rc<RefCell<PanelState>>);
+
+impl Panel {
+    pub fn new(controllers: usize) -> Self {
+        Panel(Rc::new(RefCell::new(PanelState {
+            controllers: (0..controllers).map(|_| Controller::new()).collect(),
+            selected: Controllers::First,
+            backlight: None,
+        })))
+    }
+
+    pub fn controller<R>(&self, index: usize, f: impl FnOnce(&Controller) -> R) -> R {
+        f(&self.0.borrow().controllers[index])
+    }
+
+    /// `len` DDRAM bytes of the first controller from `address`, as text
+    pub fn text(&self, address: u8, len: usize) -> std::string::String {
+        self.controller(0, |c| c.text(address, len))
+    }
+
+    /// Forget the commands and nibbles received so far
+    pub fn clear_commands(&self) {
+        for controller in &mut self.0.borrow_mut().controllers {
+            controller.commands.clear();
+            controller.nibbles.clear();
+        }
+    }
+
+    fn each(&self, f: impl Fn(&mut Controller)) {
+        let mut state = self.0.borrow_mut();
+        let selected = state.selected;
+        for (index, controller) in state.controllers.iter_mut().enumerate() {
+            let chosen = match selected {
+                Controllers::First => index == 0,
+                Controllers::Second => index == 1,
+                Controllers::Both => true,
+            };
+            if chosen {
+                f(controller);
+            }
+        }
+    }
+}
+
+impl LcdBus for Panel {
+    type Error = core::convert::Infallible;
+
+    fn write_nibble(
+        &mut self,
+        value: u8,
+        _register: Register,
+        _wait: &mut impl WaitStrategy,
+    ) -> Result<(), Self::Error> {
+        self.each(|c| c.nibbles.push(value));
+        Ok(())
+    }
+
+    fn write_byte(
+        &mut self,
+        value: u8,
+        register: Register,
+        _wait: &mut impl WaitStrategy,
+    ) -> Result<(), Self::Error> {
+        match register {
+            Register::Command => self.each(|c| c.command(value)),
+            Register::Data => self.each(|c| c.data(value)),
+        }
+        Ok(())
+    }
+
+    fn set_backlight(&mut self, backlight: Backlight) -> Result<(), Self::Error> {
+        self.0.borrow_mut().backlight = Some(backlight);
+        Ok(())
+    }
+
+    fn controllers(&self) -> u8 {
+        self.0.borrow().controllers.len() as u8
+    }
+
+    fn select(&mut self, controllers: Controllers) {
+        self.0.borrow_mut().selected = controllers;
+    }
+}
+
+/// I2C bus recording the bytes of every write, shared with the test
+#[derive(Clone, Default)]
+pub struct Recorder(pub Rc<RefCell<Vec<u8>>>);
+
+impl Recorder {
+    pub fn take(&self) -> Vec<u8> {
+        core::mem::take(&mut *self.0.borrow_mut())
+    }
+}
+
+impl i2c::ErrorType for Recorder {
+    type Error = core::convert::Infallible;
+}
+
+impl I2c for Recorder {
+    fn transaction(
+        &mut self,
+        _address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        for operation in operations {
+            match operation {
+                Operation::Write(bytes) => self.0.borrow_mut().extend_from_slice(bytes),
+                Operation::Read(buf) => buf.fill(0),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Output pin logging its levels, with its `id`, in a log shared by pins
+pub struct Pin {
+    id: u8,
+    log: Rc<RefCell<Vec<(u8, bool)>>>,
+}
+
+impl Pin {
+    pub fn new(id: u8, log: &Rc<RefCell<Vec<(u8, bool)>>>) -> Self {
+        Pin {
+            id,
+            log: log.clone(),
+        }
+    }
+}
+
+impl digital::ErrorType for Pin {
+    type Error = core::convert::Infallible;
+}
+
+impl OutputPin for Pin {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.log.borrow_mut().push((self.id, false));
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.log.borrow_mut().push((self.id, true));
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use common::{NoDelay, Pin};
+use i2c_lcd_screen::{Contrast, X9c10x};
+
+const CS: u8 = 0;
+const INC: u8 = 1;
+const UD: u8 = 2;
+
+// Falling edges of INC while the chip is selected, which each move the wiper
+fn steps(log: &[(u8, bool)]) -> usize {
+    let (mut cs, mut inc) = (true, true);
+    let mut steps = 0;
+    for &(pin, high) in log {
+        match pin {
+            CS => cs = high,
+            INC => {
+                if inc && !high && !cs {
+                    steps += 1;
+                }
+                inc = high;
+            }
+            _ => {}
+        }
+    }
+    steps
+}
+
+#[test]
+fn x9c10x_moves_the_wiper_by_the_difference() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut pot = X9c10x::new(
+        Pin::new(CS, &log),
+        Pin::new(INC, &log),
+        Pin::new(UD, &log),
+        NoDelay,
+    );
+
+    // All the way down first, then up to the top
+    pot.set_contrast(255).unwrap();
+    assert_eq!(steps(&log.take()), 99 + 99);
+
+    pot.set_contrast(128).unwrap();
+    assert_eq!(steps(&log.take()), 99 - 50);
+
+    pot.set_contrast(128).unwrap();
+    assert_eq!(steps(&log.take()), 0);
+}
@@ -0,0 +1,30 @@
+mod common;
+
+use common::{NoDelay, Panel};
+use i2c_lcd_screen::{Backlight, Error, Lcd};
+
+#[test]
+fn failed_init_leaves_the_display_alone_when_dropped() {
+    let panel = Panel::new(1);
+    let result = Lcd::with_bus(panel.clone(), NoDelay, 16, 2)
+        .blank_on_drop()
+        .init_with(|_| Err(Error::Unavailable));
+
+    assert!(matches!(result, Err(Error::Unavailable)));
+    assert_eq!(panel.controller(0, |c| c.commands.len()), 0);
+    assert_eq!(panel.0.borrow().backlight, None);
+}
+
+#[test]
+fn blank_on_drop_blanks_an_initialized_display() {
+    let panel = Panel::new(1);
+    let mut lcd = Lcd::with_bus(panel.clone(), NoDelay, 16, 2)
+        .blank_on_drop()
+        .init()
+        .unwrap();
+    lcd.print("hi").unwrap();
+
+    drop(lcd);
+    assert_eq!(panel.text(0x00, 2), "  ");
+    assert_eq!(panel.0.borrow().backlight, Some(Backlight::Off));
+}
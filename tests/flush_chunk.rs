@@ -0,0 +1,39 @@
+mod common;
+
+use common::{NoDelay, Recorder};
+use i2c_lcd_screen::{Address, Half, Lcd, Pcf8575, Progress};
+
+#[test]
+fn flush_chunk_budgets_with_the_bytes_of_the_bus() {
+    let i2c = Recorder::default();
+    let bus = Pcf8575::new(i2c.clone(), Address::PCF8575, Half::Low);
+    let mut lcd = Lcd::with_bus(bus, NoDelay, 16, 2).init().unwrap();
+    lcd.set_buffering(true).unwrap();
+    lcd.print("abcd").unwrap();
+    i2c.take();
+
+    // 12 bytes for each command or character on a PCF8575: the address and
+    // the first cell, then the cursor moved back
+    assert_eq!(lcd.flush_chunk(36).unwrap(), Progress::Pending { cells: 1 });
+    assert_eq!(i2c.take().len(), 36);
+
+    assert_eq!(lcd.flush_chunk(60).unwrap(), Progress::Done { cells: 3 });
+    assert_eq!(i2c.take().len(), 60);
+}
+
+#[test]
+fn flush_chunk_sends_a_cell_below_the_cost_of_one() {
+    let i2c = Recorder::default();
+    let bus = Pcf8575::new(i2c.clone(), Address::PCF8575, Half::Low);
+    let mut lcd = Lcd::with_bus(bus, NoDelay, 16, 2).init().unwrap();
+    lcd.set_buffering(true).unwrap();
+    lcd.print("ab").unwrap();
+    i2c.take();
+
+    // Less than the address and a cell: one cell each call all the same
+    assert_eq!(lcd.flush_chunk(20).unwrap(), Progress::Pending { cells: 1 });
+    assert_eq!(i2c.take().len(), 36);
+
+    assert_eq!(lcd.flush_chunk(0).unwrap(), Progress::Done { cells: 1 });
+    assert_eq!(i2c.take().len(), 36);
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use common::{NoDelay, Panel, Recorder};
+use i2c_lcd_screen::{Dots, Lcd, Lines, St7032i};
+
+#[test]
+fn one_line_makes_a_single_row() {
+    let panel = Panel::new(1);
+    let lcd = Lcd::with_bus(panel.clone(), NoDelay, 16, 2)
+        .lines(Lines::OneLine)
+        .init()
+        .unwrap();
+
+    assert_eq!(lcd.rows(), 1);
+    assert_eq!(panel.controller(0, |c| c.function_sets()), [0x20]);
+}
+
+#[test]
+fn tall_font_makes_a_single_row() {
+    let panel = Panel::new(1);
+    let mut lcd = Lcd::with_bus(panel.clone(), NoDelay, 16, 2)
+        .font(Dots::Dots5x10)
+        .init()
+        .unwrap();
+
+    assert_eq!(lcd.rows(), 1);
+    assert_eq!(panel.controller(0, |c| c.function_sets()), [0x24]);
+
+    // The second row does not exist, so the cursor stays on the first one
+    lcd.set_cursor_position(2, 1).unwrap();
+    assert_eq!(lcd.cursor_position(), (2, 0));
+}
+
+#[test]
+fn two_lines_again_keep_the_rows() {
+    let panel = Panel::new(1);
+    let lcd = Lcd::with_bus(panel.clone(), NoDelay, 16, 2)
+        .lines(Lines::OneLine)
+        .lines(Lines::TwoLine)
+        .init()
+        .unwrap();
+
+    assert_eq!(lcd.rows(), 2);
+    assert_eq!(panel.controller(0, |c| c.function_sets()), [0x28]);
+}
+
+#[test]
+fn short_font_again_keeps_the_rows() {
+    let panel = Panel::new(1);
+    let lcd = Lcd::with_bus(panel.clone(), NoDelay, 16, 2)
+        .font(Dots::Dots5x10)
+        .font(Dots::Dots5x8)
+        .init()
+        .unwrap();
+
+    assert_eq!(lcd.rows(), 2);
+    assert_eq!(panel.controller(0, |c| c.function_sets()), [0x28]);
+}
+
+#[test]
+fn tall_font_makes_a_single_row_whatever_the_order() {
+    for lcd in [
+        Lcd::with_bus(Panel::new(1), NoDelay, 16, 2)
+            .font(Dots::Dots5x10)
+            .lines(Lines::TwoLine),
+        Lcd::with_bus(Panel::new(1), NoDelay, 16, 2)
+            .lines(Lines::TwoLine)
+            .font(Dots::Dots5x10),
+    ] {
+        assert_eq!(lcd.init().unwrap().rows(), 1);
+    }
+}
+
+#[test]
+fn st7032i_contrast_keeps_the_function_set() {
+    let i2c = Recorder::default();
+    let mut lcd = Lcd::with_bus(St7032i::new(i2c.clone(), 0x3E), NoDelay, 16, 2)
+        .lines(Lines::OneLine)
+        .init()
+        .unwrap();
+    i2c.take();
+
+    lcd.set_contrast(0xff).unwrap();
+    // Extended instructions, contrast and power, then normal ones, each after
+    // a command control byte
+    assert_eq!(i2c.take(), [0x00, 0x31, 0x00, 0x7f, 0x00, 0x57, 0x00, 0x30]);
+}
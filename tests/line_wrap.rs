@@ -0,0 +1,36 @@
+mod common;
+
+use common::{NoDelay, Panel};
+use i2c_lcd_screen::Lcd;
+
+fn wrapping(panel: &Panel) -> Lcd<Panel, NoDelay> {
+    let mut lcd = Lcd::with_bus(panel.clone(), NoDelay, 16, 2).init().unwrap();
+    lcd.set_line_wrap(true);
+    lcd
+}
+
+#[test]
+fn left_to_right_wraps_to_the_first_column_of_the_next_row() {
+    let panel = Panel::new(1);
+    let mut lcd = wrapping(&panel);
+
+    lcd.set_cursor_position(14, 0).unwrap();
+    lcd.print("abc").unwrap();
+    assert_eq!(panel.text(14, 2), "ab");
+    assert_eq!(panel.text(0x40, 1), "c");
+    assert_eq!(lcd.char_at(0, 1), Some('c'));
+}
+
+#[test]
+fn right_to_left_wraps_to_the_last_column_of_the_next_row() {
+    let panel = Panel::new(1);
+    let mut lcd = wrapping(&panel);
+
+    lcd.right_to_left().unwrap();
+    lcd.set_cursor_position(1, 0).unwrap();
+    lcd.print("abc").unwrap();
+    assert_eq!(panel.text(0, 2), "ba");
+    assert_eq!(panel.text(0x40 + 15, 1), "c");
+    assert_eq!(lcd.char_at(15, 1), Some('c'));
+    assert_eq!(lcd.cursor_position(), (14, 1));
+}
@@ -0,0 +1,20 @@
+mod common;
+
+use common::{NoDelay, Panel};
+use i2c_lcd_screen::{Lcd, Pattern, RowOffsets};
+
+#[test]
+fn address_map_wraps_around_the_ddram() {
+    let panel = Panel::new(1);
+    let mut lcd = Lcd::with_bus(panel.clone(), NoDelay, 4, 2)
+        .row_offsets(RowOffsets([0x10, 0x7e, 0x00, 0x00]))
+        .init()
+        .unwrap();
+
+    lcd.show_test_pattern(Pattern::AddressMap).unwrap();
+
+    // The second row runs from 0x7e into 0x00
+    assert_eq!(panel.text(0x7e, 2), "7E");
+    assert_eq!(panel.text(0x00, 2), "01");
+    assert_eq!(panel.text(0x10, 4), "1023");
+}
@@ -0,0 +1,52 @@
+mod common;
+
+use common::{NoDelay, Recorder};
+use i2c_lcd_screen::{Address, Backlight, Half, Lcd, Pcf8575, PinMapping};
+
+#[test]
+fn pcf8575_follows_the_pin_mapping() {
+    let i2c = Recorder::default();
+    let bus =
+        Pcf8575::new(i2c.clone(), Address::PCF8575, Half::High).pin_mapping(PinMapping::MJKDZ);
+    let mut lcd = Lcd::with_bus(bus, NoDelay, 16, 2).init().unwrap();
+    i2c.take();
+
+    // RS on P6, E on P4, D4–D7 on P0–P3 and the backlight on P7, active low,
+    // on the high port, after the other one
+    lcd.write(b'A').unwrap();
+    assert_eq!(
+        i2c.take(),
+        [
+            0xff, 0x44, 0xff, 0x54, 0xff, 0x44, //
+            0xff, 0x41, 0xff, 0x51, 0xff, 0x41,
+        ]
+    );
+
+    lcd.set_backlight(Backlight::Off).unwrap();
+    // The display lines stay as last written
+    assert_eq!(i2c.take(), [0xff, 0xc1]);
+}
+
+#[test]
+fn write_other_keeps_the_display_lines() {
+    let i2c = Recorder::default();
+    let bus = Pcf8575::new(i2c.clone(), Address::PCF8575, Half::Low);
+    let mut lcd = Lcd::with_bus(bus, NoDelay, 16, 2).init().unwrap();
+
+    lcd.write(b'A').unwrap();
+    i2c.take();
+
+    // RS stays high and the backlight on, on the low port, before the other one
+    lcd.write_other(0x0f).unwrap();
+    assert_eq!(i2c.take(), [0x19, 0x0f]);
+
+    lcd.write(b'B').unwrap();
+    assert_eq!(
+        i2c.take(),
+        [
+            0x49, 0x0f, 0x4d, 0x0f, 0x49, 0x0f, //
+            0x29, 0x0f, 0x2d, 0x0f, 0x29, 0x0f,
+        ]
+    );
+    assert_eq!(lcd.read_other().unwrap(), 0);
+}
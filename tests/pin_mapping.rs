@@ -0,0 +1,21 @@
+mod common;
+
+use i2c_lcd_screen::{PinMapping, Polarity};
+
+#[test]
+fn new_builds_the_given_layout() {
+    let mapping = PinMapping::new(6, 5, 4, 7, [0, 1, 2, 3], Polarity::ActiveLow);
+    assert_eq!(mapping, PinMapping::MJKDZ);
+}
+
+#[test]
+#[should_panic(expected = "expander bit above 7")]
+fn new_rejects_bits_above_7() {
+    PinMapping::new(0, 1, 2, 8, [4, 5, 6, 7], Polarity::ActiveHigh);
+}
+
+#[test]
+#[should_panic(expected = "expander bit used twice")]
+fn new_rejects_bits_used_twice() {
+    PinMapping::new(0, 1, 2, 3, [4, 5, 6, 2], Polarity::ActiveHigh);
+}
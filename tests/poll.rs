@@ -0,0 +1,27 @@
+mod common;
+
+use common::{NoDelay, Panel};
+use i2c_lcd_screen::{Error, Lcd};
+
+#[test]
+fn print_between_polls_lands_at_the_cursor() {
+    let panel = Panel::new(1);
+    let mut lcd = Lcd::with_bus(panel.clone(), NoDelay, 16, 2).init().unwrap();
+
+    lcd.start_print("abcd").unwrap();
+    assert!(matches!(lcd.poll(0), Err(Error::WouldBlock)));
+    lcd.print("XY").unwrap();
+
+    let mut now = 0;
+    loop {
+        now += 50;
+        match lcd.poll(now) {
+            Ok(()) => break,
+            Err(Error::WouldBlock) => continue,
+            Err(e) => panic!("{e:?}"),
+        }
+    }
+    lcd.print("Z").unwrap();
+
+    assert_eq!(panel.text(0, 7), "abcdXYZ");
+}
@@ -0,0 +1,26 @@
+mod common;
+
+use common::{NoDelay, Panel};
+use i2c_lcd_screen::Lcd;
+
+#[test]
+fn reinitialize_wakes_both_controllers_of_40x4() {
+    let panel = Panel::new(2);
+    let mut lcd = Lcd::with_bus(panel.clone(), NoDelay, 40, 4).init().unwrap();
+    lcd.set_cursor_position(3, 3).unwrap();
+    lcd.print("ab").unwrap();
+    panel.clear_commands();
+
+    lcd.reinitialize().unwrap();
+    for controller in 0..2 {
+        assert_eq!(
+            panel.controller(controller, |c| c.nibbles.clone()),
+            [0x30, 0x30, 0x30, 0x20]
+        );
+        assert_eq!(panel.controller(controller, |c| c.function_sets()), [0x28]);
+    }
+
+    lcd.print("cd").unwrap();
+    assert_eq!(panel.controller(1, |c| c.text(0x43, 4)), "abcd");
+    assert_eq!(panel.controller(0, |c| c.text(0x43, 4)), "    ");
+}
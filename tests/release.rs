@@ -0,0 +1,23 @@
+mod common;
+
+use common::{NoDelay, Panel, Recorder};
+use i2c_lcd_screen::{Address, Lcd};
+
+#[test]
+fn release_gives_back_the_i2c_bus() {
+    let i2c = Recorder::default();
+    let lcd = Lcd::new(i2c.clone(), Address::PCF8574, NoDelay, 16, 2)
+        .init()
+        .unwrap();
+
+    let (bus, _delay): (Recorder, NoDelay) = lcd.release();
+    assert!(!bus.take().is_empty());
+}
+
+#[test]
+fn into_parts_gives_back_the_bus() {
+    let lcd = Lcd::with_bus(Panel::new(1), NoDelay, 16, 2).init().unwrap();
+
+    let (panel, _delay): (Panel, NoDelay) = lcd.into_parts();
+    assert_eq!(panel.controller(0, |c| c.function_sets()), [0x28]);
+}
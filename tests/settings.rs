@@ -0,0 +1,77 @@
+mod common;
+
+use common::{NoDelay, Panel};
+use i2c_lcd_screen::{Lcd, RomVariant, Settings};
+
+const HEART: [u8; 8] = [0x00, 0x0a, 0x1f, 0x1f, 0x0e, 0x04, 0x00, 0x00];
+
+fn configured(panel: &Panel) -> Lcd<Panel, NoDelay> {
+    let mut lcd = Lcd::with_bus(panel.clone(), NoDelay, 16, 2).init().unwrap();
+    lcd.load_glyphs(&[('♥', HEART)]).unwrap();
+    lcd.set_rom_variant(RomVariant::A02);
+    lcd.set_replacement_char(b'#');
+    lcd
+}
+
+// Print a mapped char, a Latin-1 one and one neither ROM has
+fn check_charset(panel: &Panel, lcd: &mut Lcd<Panel, NoDelay>) {
+    lcd.print("♥é✓").unwrap();
+    assert_eq!(
+        panel.controller(0, |c| c.ddram[..3].to_vec()),
+        [0, 0xe9, b'#']
+    );
+    assert_eq!(panel.controller(0, |c| c.cgram[..8].to_vec()), HEART);
+}
+
+#[test]
+fn settings_round_trip_keeps_the_charset() {
+    let settings = configured(&Panel::new(1)).settings();
+    let restored = Settings::from_bytes(&settings.to_bytes()).unwrap();
+    assert_eq!(restored.custom, settings.custom);
+    assert_eq!(restored.rom, RomVariant::A02);
+    assert_eq!(restored.replacement, b'#');
+    assert_eq!(restored.contrast, None);
+
+    let panel = Panel::new(1);
+    let mut lcd = Lcd::with_bus(panel.clone(), NoDelay, 16, 2).init().unwrap();
+    lcd.apply_settings(&restored).unwrap();
+    check_charset(&panel, &mut lcd);
+}
+
+#[cfg(feature = "embedded-storage")]
+#[test]
+fn load_settings_restores_the_charset() {
+    use embedded_storage::{ReadStorage, Storage};
+
+    struct Ram([u8; Settings::SIZE]);
+
+    impl ReadStorage for Ram {
+        type Error = ();
+
+        fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), ()> {
+            bytes.copy_from_slice(&self.0[offset as usize..][..bytes.len()]);
+            Ok(())
+        }
+
+        fn capacity(&self) -> usize {
+            self.0.len()
+        }
+    }
+
+    impl Storage for Ram {
+        fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), ()> {
+            self.0[offset as usize..][..bytes.len()].copy_from_slice(bytes);
+            Ok(())
+        }
+    }
+
+    let mut ram = Ram([0; Settings::SIZE]);
+    configured(&Panel::new(1))
+        .save_settings(&mut ram, 0)
+        .unwrap();
+
+    let panel = Panel::new(1);
+    let mut lcd = Lcd::with_bus(panel.clone(), NoDelay, 16, 2).init().unwrap();
+    lcd.load_settings(&mut ram, 0).unwrap();
+    check_charset(&panel, &mut lcd);
+}
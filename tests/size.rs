@@ -0,0 +1,19 @@
+mod common;
+
+use common::{NoDelay, Panel};
+use i2c_lcd_screen::{Lcd16x2, Lcd20x4, RowOffsets};
+
+#[test]
+fn aliases_fix_the_geometry() {
+    assert_eq!(Lcd16x2::ROW_OFFSETS, RowOffsets::LCD_16X2);
+    assert_eq!(Lcd20x4::ROW_OFFSETS, RowOffsets::LCD_20X4);
+
+    let panel = Panel::new(1);
+    let mut lcd = Lcd20x4::with_bus(panel.clone(), NoDelay).init().unwrap();
+    assert_eq!(lcd.geometry(), (20, 4));
+
+    let (col, row) = Lcd20x4::position::<19, 3>();
+    lcd.set_cursor_position(col, row).unwrap();
+    lcd.print("x").unwrap();
+    assert_eq!(panel.text(0x54 + 19, 1), "x");
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use common::{NoDelay, Panel};
+use i2c_lcd_screen::Lcd;
+
+// Print `s` right to left from `col`, `row`, buffering or not
+fn print_right_to_left(buffering: bool, col: u8, row: u8, s: &str) -> (Panel, Lcd<Panel, NoDelay>) {
+    let panel = Panel::new(1);
+    let mut lcd = Lcd::with_bus(panel.clone(), NoDelay, 16, 2).init().unwrap();
+
+    lcd.right_to_left().unwrap();
+    lcd.set_cursor_position(col, row).unwrap();
+    lcd.set_buffering(buffering).unwrap();
+    lcd.print(s).unwrap();
+    lcd.flush().unwrap();
+    (panel, lcd)
+}
+
+#[test]
+fn right_to_left_clips_past_first_column() {
+    let (panel, lcd) = print_right_to_left(true, 1, 1, "abc");
+
+    assert_eq!(lcd.cursor_position(), (0, 1));
+    assert_eq!(panel.text(0x40, 2), "ba");
+    assert_eq!(lcd.char_at(0, 1), Some('b'));
+    assert_eq!(lcd.char_at(1, 1), Some('a'));
+}
+
+#[test]
+fn right_to_left_past_first_column_without_buffering() {
+    let (panel, lcd) = print_right_to_left(false, 0, 1, "ab");
+
+    assert_eq!(lcd.cursor_position(), (0, 1));
+    assert_eq!(panel.text(0x40, 1), "a");
+    assert_eq!(lcd.char_at(0, 1), Some('a'));
+}
+
+#[test]
+fn right_to_left_buffering_matches_direct_writes() {
+    let (buffered, buffered_lcd) = print_right_to_left(true, 2, 0, "abcde");
+    let (direct, direct_lcd) = print_right_to_left(false, 2, 0, "abcde");
+
+    assert_eq!(buffered.text(0, 0x68), direct.text(0, 0x68));
+    assert_eq!(buffered.text(0, 3), "cba");
+    assert_eq!(buffered_lcd.content_hash(), direct_lcd.content_hash());
+}
+
+#[test]
+fn left_to_right_after_the_left_edge_starts_from_the_first_column() {
+    let (panel, mut lcd) = print_right_to_left(false, 0, 0, "ab");
+
+    lcd.left_to_right().unwrap();
+    lcd.print("cd").unwrap();
+    assert_eq!(panel.text(0, 2), "cd");
+    assert_eq!(lcd.char_at(1, 0), Some('d'));
+}